@@ -4,15 +4,15 @@
 
 //! A fixed-width text field.
 
+use line_ui::Style;
 use line_ui::element::{Cursor, Direction, Gap, IntoElement};
-use line_ui::{Renderer, Style};
+use line_ui::session::Session;
 use termion::event::{Event, Key};
 use termion::input::TermRead;
-use termion::raw::IntoRawMode;
 
 fn main() -> std::io::Result<()> {
-    let stdout = std::io::stdout().into_raw_mode()?;
-    let mut r = Renderer::new(stdout);
+    let mut session = Session::new()?;
+    let r = session.renderer();
 
     let mut name = String::new();
 
@@ -41,8 +41,7 @@ fn main() -> std::io::Result<()> {
         }
     }
 
-    r.clear()?;
-    drop(r);
+    drop(session);
     println!("Your name is {name:?}");
     Ok(())
 }
@@ -6,11 +6,11 @@
 
 use std::io;
 
-use line_ui::element::{Cursor, Element, IntoElement};
-use line_ui::{Renderer, Style};
+use line_ui::Style;
+use line_ui::element::{BoxElement, Cursor, Element, IntoElement};
+use line_ui::session::Session;
 use termion::event::{Event, Key};
 use termion::input::TermRead;
-use termion::raw::IntoRawMode;
 
 #[derive(Debug, Clone, Copy, Default)]
 enum Player {
@@ -67,48 +67,34 @@ impl TicTacToe {
 }
 
 fn main() -> io::Result<()> {
-    let stdout = io::stdout().into_raw_mode()?;
-    let mut r = Renderer::new(stdout);
+    let mut session = Session::new()?;
+    let r = session.renderer();
     let mut events = std::io::stdin().events();
 
     let mut game = TicTacToe::default();
     let (mut row, mut col): (usize, usize) = (1, 1);
     loop {
         // Render the grid
-        r.reset()?;
-        for (i, line) in game.grid.iter().enumerate() {
-            if i != 0 {
-                r.render("--+---+--".into_element())?;
-            }
-            r.render((
-                ((row, col) == (i, 0)).then_some(Cursor),
-                render_player(line[0]),
-                " | ".into_element(),
-                ((row, col) == (i, 1)).then_some(Cursor),
-                render_player(line[1]),
-                " | ".into_element(),
-                ((row, col) == (i, 2)).then_some(Cursor),
-                render_player(line[2]),
-            ))?;
-        }
+        let mut frame = r.frame()?;
+        frame.render_block(render_grid(&game, (row, col)))?;
 
         // Check if someone won
         let result = game.check_win();
         match result {
             Some(Some(winner)) => {
-                r.render((
+                frame.render((
                     "The winner is ".into_element(),
                     render_player(Some(winner)),
                     "!".into_element(),
                 ))?;
             }
             Some(None) => {
-                r.render("The game is a draw.".into_element())?;
+                frame.render("The game is a draw.".into_element())?;
             }
             _ => {}
         }
 
-        r.finish()?;
+        frame.finish()?;
         if result.is_some() {
             r.leave()?;
             break;
@@ -131,6 +117,29 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+fn render_grid(game: &TicTacToe, cursor: (usize, usize)) -> Vec<BoxElement<'static>> {
+    let mut lines = Vec::new();
+    for (i, line) in game.grid.iter().enumerate() {
+        if i != 0 {
+            lines.push("--+---+--".boxed());
+        }
+        lines.push(
+            (
+                (cursor == (i, 0)).then_some(Cursor),
+                render_player(line[0]),
+                " | ".into_element(),
+                (cursor == (i, 1)).then_some(Cursor),
+                render_player(line[1]),
+                " | ".into_element(),
+                (cursor == (i, 2)).then_some(Cursor),
+                render_player(line[2]),
+            )
+                .boxed(),
+        );
+    }
+    lines
+}
+
 fn render_player(player: Option<Player>) -> impl Element<'static> {
     match player {
         None => "-".styled(Style::fg(245)),
@@ -0,0 +1,59 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+//! The [`Block`] trait, for widgets that span multiple lines.
+
+mod boxed;
+mod column;
+mod impls;
+mod row;
+
+use crate::element::Element;
+
+pub use boxed::*;
+pub use column::*;
+pub use row::*;
+
+/// A widget that consists of multiple lines, such as a list or a table.
+///
+/// Each line of a block is an [`Element`]. A block can be rendered with
+/// [`Renderer::render_block`](crate::Renderer::render_block).
+///
+/// The simplest block is a [`Vec`] of elements, one for each line.
+///
+/// # Example
+///
+/// ```
+/// use line_ui::Renderer;
+/// use line_ui::block::Block;
+/// use line_ui::element::{Element, IntoElement};
+///
+/// struct List<'a>(&'a [&'a str]);
+///
+/// impl<'s> Block<'s> for List<'s> {
+///     fn height(&self) -> usize {
+///         self.0.len()
+///     }
+///
+///     fn lines(&self) -> impl Iterator<Item = impl Element<'s>> {
+///         self.0.iter().map(|item| ("- ".into_element(), item.into_element()))
+///     }
+/// }
+///
+/// # fn main() -> std::io::Result<()> {
+/// let mut r = Renderer::new(vec![]);
+/// r.reset()?
+///     .render("Shopping list:".into_element())?
+///     .render_block(List(&["apples", "oranges"]))?
+///     .finish()?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Block<'s> {
+    /// The number of lines in the block.
+    fn height(&self) -> usize;
+
+    /// Renders the block into a sequence of lines, from top to bottom.
+    fn lines(&self) -> impl Iterator<Item = impl Element<'s>>;
+}
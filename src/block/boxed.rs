@@ -0,0 +1,180 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::block::Block;
+use crate::element::Element;
+use crate::render::RenderChunk;
+
+/// Renders every line of a block, within the given width if there is one.
+type RenderLines<'s> = Box<dyn Fn(Option<usize>) -> Vec<Vec<RenderChunk<'s>>> + 's>;
+
+/// A block with its type erased.
+///
+/// This type can be used to return multiple kinds of blocks from a function,
+/// or to store blocks of different types together. The lines of the block are
+/// rendered when they are needed, so that they can adapt to the width that
+/// they are given with [`Element::render_in`].
+///
+/// # Example
+///
+/// ```
+/// use line_ui::block::BoxBlock;
+/// use line_ui::element::IntoElement;
+///
+/// fn status(errors: &[&'static str]) -> BoxBlock<'static> {
+///     if errors.is_empty() {
+///         BoxBlock::new(vec!["ok".into_element()])
+///     } else {
+///         BoxBlock::new(errors.iter().map(|error| error.into_element()).collect::<Vec<_>>())
+///     }
+/// }
+/// ```
+pub struct BoxBlock<'s> {
+    /// The width of each line.
+    widths: Vec<usize>,
+    width: usize,
+    render_lines: RenderLines<'s>,
+    /// The rendered lines for each width that has been requested, so that the
+    /// inner block's lines are only walked once per width.
+    rendered: RefCell<HashMap<Option<usize>, Vec<Vec<RenderChunk<'s>>>>>,
+}
+
+impl<'s> BoxBlock<'s> {
+    /// Boxes the provided block.
+    pub fn new<B: Block<'s> + 's>(inner: B) -> Self {
+        let widths: Vec<_> = inner.lines().map(|line| line.width()).collect();
+        debug_assert_eq!(widths.len(), inner.height());
+        let width = widths.iter().copied().max().unwrap_or(0);
+        let render_lines = Box::new(move |max_width| {
+            inner
+                .lines()
+                .map(|line| match max_width {
+                    Some(max_width) => line.render_in(max_width).collect(),
+                    None => line.render().collect(),
+                })
+                .collect()
+        });
+        BoxBlock {
+            widths,
+            width,
+            render_lines,
+            rendered: RefCell::default(),
+        }
+    }
+
+    /// Renders the line at the given index, within the given width if there
+    /// is one.
+    fn render_line(&self, index: usize, max_width: Option<usize>) -> Vec<RenderChunk<'s>> {
+        let mut rendered = self.rendered.borrow_mut();
+        let lines = rendered
+            .entry(max_width)
+            .or_insert_with(|| (self.render_lines)(max_width));
+        lines.get(index).cloned().unwrap_or_default()
+    }
+
+    /// Returns the width of the widest line.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the line at the given index, if there is one.
+    pub(crate) fn line(&self, index: usize) -> Option<BoxLine<'_, 's>> {
+        (index < self.widths.len()).then_some(BoxLine { block: self, index })
+    }
+}
+
+impl<'s> Block<'s> for BoxBlock<'s> {
+    fn height(&self) -> usize {
+        self.widths.len()
+    }
+
+    fn lines(&self) -> impl Iterator<Item = impl Element<'s>> {
+        (0..self.widths.len()).map(|index| BoxLine { block: self, index })
+    }
+}
+
+/// A line of a [`BoxBlock`].
+#[derive(Clone, Copy)]
+pub(crate) struct BoxLine<'a, 's> {
+    block: &'a BoxBlock<'s>,
+    index: usize,
+}
+
+impl<'s> Element<'s> for BoxLine<'_, 's> {
+    fn width(&self) -> usize {
+        self.block.widths[self.index]
+    }
+
+    fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        self.block.render_line(self.index, None).into_iter()
+    }
+
+    fn render_in(&self, max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        self.block
+            .render_line(self.index, Some(max_width))
+            .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::block::Column;
+    use crate::element::{Abbreviated, Gap};
+
+    use super::*;
+
+    #[test]
+    fn lazy_lines() {
+        let block = BoxBlock::new(vec![Abbreviated("hello", "hi")]);
+        assert_eq!(block.width(), 5);
+        let line = block.line(0).unwrap();
+        assert_eq!(line.width(), 5);
+        assert_eq!(line.render().collect::<Vec<_>>(), ["hello".into()]);
+        assert_eq!(line.render_in(3).collect::<Vec<_>>(), ["hi".into()]);
+        assert!(block.line(1).is_none());
+    }
+
+    /// A block whose lines are produced by `map`, counting how many lines
+    /// have been produced.
+    struct Counted<'a> {
+        height: usize,
+        produced: &'a Cell<usize>,
+    }
+
+    impl<'s> Block<'s> for Counted<'_> {
+        fn height(&self) -> usize {
+            self.height
+        }
+
+        fn lines(&self) -> impl Iterator<Item = impl Element<'s>> {
+            (0..self.height).map(|index| {
+                self.produced.set(self.produced.get() + 1);
+                Gap(index % 10)
+            })
+        }
+    }
+
+    #[test]
+    fn linear_rendering() {
+        let produced = Cell::new(0);
+        let block = Counted {
+            height: 1000,
+            produced: &produced,
+        };
+        let column = Column::new().with_block(block);
+        for line in column.lines() {
+            line.render_in(80).for_each(drop);
+        }
+        for line in column.lines() {
+            line.render_in(5).for_each(drop);
+        }
+        // Once to measure the lines, and once for each width.
+        assert_eq!(produced.get(), 3000);
+    }
+}
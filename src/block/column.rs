@@ -0,0 +1,152 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+use crate::block::{Block, BoxBlock, BoxLine};
+use crate::element::{Element, FixedWidth};
+use crate::render::RenderChunk;
+
+/// A block that stacks other blocks vertically.
+///
+/// Each line is padded to the width of the widest line, so that the column
+/// has a consistent width, e.g., when it is placed in a [`Row`](super::Row).
+///
+/// # Example
+///
+/// ```
+/// use line_ui::block::Column;
+/// use line_ui::element::IntoElement;
+///
+/// let column = Column::new()
+///     .with_block(vec!["Header".into_element()])
+///     .with_block(vec!["one".into_element(), "two".into_element()])
+///     .with_spacing(1);
+/// ```
+#[derive(Default)]
+pub struct Column<'s> {
+    blocks: Vec<BoxBlock<'s>>,
+    spacing: usize,
+    width: usize,
+}
+
+impl<'s> Column<'s> {
+    /// Creates a new, empty [`Column`].
+    pub fn new() -> Self {
+        Column::default()
+    }
+
+    /// Adds a block to the bottom of the column.
+    pub fn with_block<B: Block<'s> + 's>(mut self, block: B) -> Self {
+        let block = BoxBlock::new(block);
+        self.width = self.width.max(block.width());
+        self.blocks.push(block);
+        self
+    }
+
+    /// Sets the number of blank lines between consecutive blocks.
+    pub fn with_spacing(mut self, spacing: usize) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Returns the width of the widest line.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+
+impl<'s> Block<'s> for Column<'s> {
+    fn height(&self) -> usize {
+        let spacing = self.spacing * self.blocks.len().saturating_sub(1);
+        self.blocks
+            .iter()
+            .map(|block| block.height())
+            .sum::<usize>()
+            + spacing
+    }
+
+    fn lines(&self) -> impl Iterator<Item = impl Element<'s>> {
+        self.blocks.iter().enumerate().flat_map(|(index, block)| {
+            let spacing = if index == 0 { 0 } else { self.spacing };
+            let lines = (0..block.height()).map(|index| block.line(index));
+            std::iter::repeat_n(None, spacing)
+                .chain(lines)
+                .map(|line| ColumnLine {
+                    width: self.width,
+                    line,
+                })
+        })
+    }
+}
+
+/// A line of a [`Column`], padded to the width of the column, or a blank line
+/// between blocks.
+struct ColumnLine<'a, 's> {
+    width: usize,
+    line: Option<BoxLine<'a, 's>>,
+}
+
+impl<'s> Element<'s> for ColumnLine<'_, 's> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        self.render_in(usize::MAX)
+    }
+
+    fn render_in(&self, max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        let line = FixedWidth::new(self.width.min(max_width), self.line);
+        line.render().collect::<Vec<_>>().into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Renderer;
+    use crate::element::{Abbreviated, IntoElement};
+
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let column = Column::new().with_spacing(1);
+        assert_eq!(column.height(), 0);
+        assert_eq!(column.width(), 0);
+        assert_eq!(column.lines().count(), 0);
+    }
+
+    #[test]
+    fn spacing() -> crate::Result<()> {
+        let column = Column::new()
+            .with_block(vec!["trans".into_element()])
+            .with_block(vec!["enby".into_element(), "rights".into_element()])
+            .with_spacing(1);
+        assert_eq!(column.height(), 4);
+        assert_eq!(column.width(), 6);
+        assert!(column.lines().all(|line| line.width() == 6));
+
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?.render_block(&column)?.finish()?;
+        assert_eq!(
+            r.writer,
+            b"\rtrans \x1b[K\n\r      \x1b[K\n\renby  \x1b[K\n\rrights\x1b[K\x1b[?25l",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn render_in() {
+        let column = Column::new()
+            .with_block(vec![Abbreviated("hello", "hi")])
+            .with_block(vec!["ab".into_element()]);
+        let lines: Vec<Vec<_>> = column
+            .lines()
+            .map(|line| line.render_in(3).collect())
+            .collect();
+        assert_eq!(
+            lines,
+            [vec!["hi".into(), " ".into()], vec!["ab".into(), " ".into()]]
+        );
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+use crate::block::Block;
+use crate::element::Element;
+
+impl<'s, B: Block<'s> + ?Sized> Block<'s> for &'_ B {
+    fn height(&self) -> usize {
+        (*self).height()
+    }
+
+    fn lines(&self) -> impl Iterator<Item = impl Element<'s>> {
+        (*self).lines()
+    }
+}
+
+impl<'s, E: Element<'s>> Block<'s> for Vec<E> {
+    fn height(&self) -> usize {
+        self.len()
+    }
+
+    fn lines(&self) -> impl Iterator<Item = impl Element<'s>> {
+        self.iter()
+    }
+}
+
+impl<'s, B: Block<'s>> Block<'s> for Option<B> {
+    fn height(&self) -> usize {
+        match self {
+            Some(inner) => inner.height(),
+            None => 0,
+        }
+    }
+
+    fn lines(&self) -> impl Iterator<Item = impl Element<'s>> {
+        self.iter().flat_map(|block| block.lines())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::{Gap, IntoElement};
+
+    use super::*;
+
+    fn is_block<'s, B: Block<'s> + ?Sized>() {}
+    fn value_is_block<'s, B: Block<'s> + ?Sized>(_: &B) {}
+
+    #[test]
+    fn block_impls() {
+        is_block::<Vec<Gap>>();
+        is_block::<&Vec<Gap>>();
+        is_block::<Option<Vec<Gap>>>();
+
+        let not_static = "foo".to_owned();
+        let lines = vec![not_static[..].into_element(), "bar".into_element()];
+        value_is_block(&lines);
+        value_is_block(&Some(&lines));
+    }
+
+    #[test]
+    fn height() {
+        let lines = vec![Gap(1), Gap(2)];
+        assert_eq!(lines.height(), 2);
+        assert_eq!(Some(&lines).height(), 2);
+        assert_eq!(None::<Vec<Gap>>.height(), 0);
+    }
+}
@@ -0,0 +1,164 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+use crate::block::{Block, BoxBlock};
+use crate::element::{Element, FixedWidth, Gap};
+use crate::render::RenderChunk;
+
+/// A block that places other blocks side by side.
+///
+/// Each block is padded to the width of its widest line, and blocks that are
+/// shorter than the others are padded with blank lines, so that every line
+/// of the row has the same width.
+///
+/// # Example
+///
+/// ```
+/// use line_ui::block::Row;
+/// use line_ui::element::IntoElement;
+///
+/// let list = vec!["main.rs".into_element(), "lib.rs".into_element()];
+/// let preview = vec!["fn main() {".into_element(), "}".into_element()];
+/// let row = Row::new().with_block(list).with_block(preview).with_spacing(2);
+/// ```
+#[derive(Default)]
+pub struct Row<'s> {
+    blocks: Vec<BoxBlock<'s>>,
+    spacing: usize,
+}
+
+impl<'s> Row<'s> {
+    /// Creates a new, empty [`Row`].
+    pub fn new() -> Self {
+        Row::default()
+    }
+
+    /// Adds a block to the right of the row.
+    pub fn with_block<B: Block<'s> + 's>(mut self, block: B) -> Self {
+        self.blocks.push(BoxBlock::new(block));
+        self
+    }
+
+    /// Sets the number of blank columns between consecutive blocks.
+    pub fn with_spacing(mut self, spacing: usize) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Returns the width of each line.
+    pub fn width(&self) -> usize {
+        let spacing = self.spacing * self.blocks.len().saturating_sub(1);
+        self.blocks.iter().map(|block| block.width()).sum::<usize>() + spacing
+    }
+}
+
+impl<'s> Block<'s> for Row<'s> {
+    fn height(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| block.height())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn lines(&self) -> impl Iterator<Item = impl Element<'s>> {
+        (0..self.height()).map(|index| RowLine { row: self, index })
+    }
+}
+
+/// A line of a [`Row`], consisting of the line at the same index of each
+/// block.
+struct RowLine<'a, 's> {
+    row: &'a Row<'s>,
+    index: usize,
+}
+
+impl<'s> Element<'s> for RowLine<'_, 's> {
+    fn width(&self) -> usize {
+        self.row.width()
+    }
+
+    fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        self.render_in(usize::MAX)
+    }
+
+    fn render_in(&self, max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        // Each block is given its own width, until the available width runs
+        // out.
+        let mut remaining = max_width;
+        let mut chunks = Vec::new();
+        for (index, block) in self.row.blocks.iter().enumerate() {
+            if index != 0 {
+                let spacing = self.row.spacing.min(remaining);
+                chunks.extend(Gap(spacing).render());
+                remaining -= spacing;
+            }
+            let width = block.width().min(remaining);
+            let line = block.line(self.index);
+            chunks.extend(FixedWidth::new(width, line).render());
+            remaining -= width;
+        }
+        chunks.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Renderer;
+    use crate::block::Column;
+    use crate::element::{Abbreviated, IntoElement};
+
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let row = Row::new().with_spacing(1);
+        assert_eq!(row.height(), 0);
+        assert_eq!(row.width(), 0);
+    }
+
+    #[test]
+    fn side_by_side() -> crate::Result<()> {
+        let row = Row::new()
+            .with_block(vec!["trans".into_element()])
+            .with_block(vec!["enby".into_element(), "rights".into_element()])
+            .with_spacing(1);
+        assert_eq!(row.height(), 2);
+        assert_eq!(row.width(), 12);
+        assert!(row.lines().all(|line| line.width() == 12));
+
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?.render_block(&row)?.finish()?;
+        assert_eq!(
+            r.writer,
+            b"\rtrans enby  \x1b[K\n\r      rights\x1b[K\x1b[?25l",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn nested() {
+        let column = Column::new()
+            .with_block(vec!["a".into_element()])
+            .with_block(vec!["bcd".into_element()]);
+        let row = Row::new()
+            .with_block(column)
+            .with_block(vec!["e".into_element()]);
+        assert_eq!(row.width(), 4);
+        assert_eq!(row.height(), 2);
+    }
+
+    #[test]
+    fn render_in() {
+        let row = Row::new()
+            .with_block(vec!["ab".into_element()])
+            .with_block(vec![Abbreviated("hello", "hi")])
+            .with_spacing(1);
+        let line = row.lines().next().unwrap();
+        assert_eq!(
+            line.render_in(6).collect::<Vec<_>>(),
+            ["ab".into(), " ".into(), "hi".into(), " ".into()],
+        );
+    }
+}
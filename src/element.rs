@@ -10,16 +10,20 @@ mod fixed_width;
 mod gap;
 mod impls;
 mod into;
+mod layout;
 mod styled;
 mod text;
 
 use crate::render::RenderChunk;
 
+pub(crate) use fixed_width::truncate_end;
+
 pub use boxed::*;
 pub use cursor::*;
 pub use fixed_width::*;
 pub use gap::*;
 pub use into::*;
+pub use layout::*;
 pub use styled::*;
 pub use text::*;
 
@@ -45,4 +49,46 @@ pub trait Element<'s> {
 
     /// Renders the element into a sequence of chunks.
     fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>>;
+
+    /// Renders the element into a sequence of chunks, given that only
+    /// `max_width` columns are available to display it.
+    ///
+    /// Elements can implement this method to adapt to narrow terminals, e.g.,
+    /// by abbreviating their contents. The result may be narrower than
+    /// [`width`](Self::width), which is the width that the element would
+    /// like to have. It may also be wider than `max_width`, in which case it
+    /// is truncated. Containers such as [`FixedWidth`] and [`Layout`] call
+    /// this method to tell their children how much room they have, and the
+    /// [`Renderer`](crate::Renderer) calls it with the width of the terminal.
+    ///
+    /// The default implementation calls [`render`](Self::render).
+    fn render_in(&self, max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        let _ = max_width;
+        self.render()
+    }
+}
+
+/// An element that renders its short form if its long form does not fit, for
+/// testing [`Element::render_in`].
+#[cfg(test)]
+pub(crate) struct Abbreviated(pub &'static str, pub &'static str);
+
+#[cfg(test)]
+impl Element<'static> for Abbreviated {
+    fn width(&self) -> usize {
+        crate::width(self.0)
+    }
+
+    fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'static>> {
+        std::iter::once(RenderChunk::from(self.0))
+    }
+
+    fn render_in(&self, max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'static>> {
+        let value = if self.width() <= max_width {
+            self.0
+        } else {
+            self.1
+        };
+        std::iter::once(RenderChunk::from(value))
+    }
 }
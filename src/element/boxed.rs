@@ -39,6 +39,14 @@ impl<'s> BoxElement<'s> {
         debug_assert_eq!(width, content.iter().map(|chunk| chunk.width).sum());
         BoxElement { width, content }
     }
+
+    /// Boxes the provided element, rendered with the given maximum width.
+    /// See [`Element::render_in`].
+    pub(crate) fn new_in<E: Element<'s>>(inner: E, max_width: usize) -> Self {
+        let content: Vec<_> = inner.render_in(max_width).collect();
+        let width = content.iter().map(|chunk| chunk.width).sum();
+        BoxElement { width, content }
+    }
 }
 
 impl<'s> Element<'s> for BoxElement<'s> {
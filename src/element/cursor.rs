@@ -2,13 +2,33 @@
  * Copyright (c) 2025 Jasmine Tai. All rights reserved.
  */
 
+use std::fmt;
+
 use crate::element::Element;
 use crate::render::RenderChunk;
 
 /// An element that places the cursor at its position.
+///
+/// The terminal's default cursor shape is used, unless a shape is given with
+/// [`with_shape`](Self::with_shape).
 #[derive(Debug, Clone, Copy)]
 pub struct Cursor;
 
+impl Cursor {
+    /// Places the cursor with the given shape.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use line_ui::element::{Cursor, CursorShape, IntoElement};
+    ///
+    /// let insert_mode = ("text".into_element(), Cursor.with_shape(CursorShape::SteadyBar));
+    /// ```
+    pub fn with_shape(self, shape: CursorShape) -> ShapedCursor {
+        ShapedCursor { shape }
+    }
+}
+
 impl<'s> Element<'s> for Cursor {
     fn width(&self) -> usize {
         0
@@ -18,3 +38,125 @@ impl<'s> Element<'s> for Cursor {
         std::iter::once(RenderChunk::CURSOR)
     }
 }
+
+/// An element that places the cursor at its position, with a particular
+/// shape. This is created by [`Cursor::with_shape`].
+#[derive(Debug, Clone, Copy)]
+pub struct ShapedCursor {
+    shape: CursorShape,
+}
+
+impl ShapedCursor {
+    /// Returns the shape of the cursor.
+    pub fn shape(&self) -> CursorShape {
+        self.shape
+    }
+}
+
+impl<'s> Element<'s> for ShapedCursor {
+    fn width(&self) -> usize {
+        0
+    }
+
+    fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        std::iter::once(RenderChunk::shaped_cursor(self.shape))
+    }
+}
+
+/// The shape of the terminal cursor.
+///
+/// The shape is set with DECSCUSR (`CSI n SP q`) when the frame is drawn, and
+/// the terminal's default shape is restored when the
+/// [`Renderer`](crate::Renderer) is cleared. Terminals that do not support
+/// cursor shapes ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorShape {
+    /// A blinking block.
+    BlinkingBlock,
+    /// A block that does not blink.
+    SteadyBlock,
+    /// A blinking underline.
+    BlinkingUnderline,
+    /// An underline that does not blink.
+    SteadyUnderline,
+    /// A blinking vertical bar.
+    BlinkingBar,
+    /// A vertical bar that does not blink.
+    SteadyBar,
+}
+
+impl CursorShape {
+    /// Returns the DECSCUSR parameter for this shape.
+    pub(crate) fn code(self) -> u16 {
+        match self {
+            CursorShape::BlinkingBlock => 1,
+            CursorShape::SteadyBlock => 2,
+            CursorShape::BlinkingUnderline => 3,
+            CursorShape::SteadyUnderline => 4,
+            CursorShape::BlinkingBar => 5,
+            CursorShape::SteadyBar => 6,
+        }
+    }
+
+    /// Returns the shape with the given DECSCUSR parameter, or `None` for the
+    /// terminal's default shape.
+    pub(crate) fn from_code(code: u16) -> Option<CursorShape> {
+        Some(match code {
+            1 => CursorShape::BlinkingBlock,
+            2 => CursorShape::SteadyBlock,
+            3 => CursorShape::BlinkingUnderline,
+            4 => CursorShape::SteadyUnderline,
+            5 => CursorShape::BlinkingBar,
+            6 => CursorShape::SteadyBar,
+            _ => return None,
+        })
+    }
+
+    /// Returns whether the cursor blinks.
+    pub fn is_blinking(self) -> bool {
+        matches!(
+            self,
+            CursorShape::BlinkingBlock | CursorShape::BlinkingUnderline | CursorShape::BlinkingBar
+        )
+    }
+}
+
+/// Writes the escape sequence that sets the cursor shape, where `None` is the
+/// terminal's default shape.
+pub(crate) struct SetCursorShape(pub(crate) Option<CursorShape>);
+
+impl fmt::Display for SetCursorShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = self.0.map_or(0, CursorShape::code);
+        write!(f, "\x1b[{code} q")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_cursor_shape() {
+        assert_eq!(SetCursorShape(None).to_string(), "\x1b[0 q");
+        assert_eq!(
+            SetCursorShape(Some(CursorShape::SteadyBar)).to_string(),
+            "\x1b[6 q",
+        );
+    }
+
+    #[test]
+    fn code() {
+        for shape in [
+            CursorShape::BlinkingBlock,
+            CursorShape::SteadyBlock,
+            CursorShape::BlinkingUnderline,
+            CursorShape::SteadyUnderline,
+            CursorShape::BlinkingBar,
+            CursorShape::SteadyBar,
+        ] {
+            assert_eq!(CursorShape::from_code(shape.code()), Some(shape));
+        }
+        assert_eq!(CursorShape::from_code(0), None);
+    }
+}
@@ -66,9 +66,9 @@ impl<'s, E: Element<'s>, T: Element<'s>> FixedWidth<E, T> {
     fn render_impl(
         &self,
         content: impl DoubleEndedIterator<Item = RenderChunk<'s>>,
+        full_content_width: usize,
         truncate: impl for<'t> Fn(RenderChunk<'t>, usize) -> RenderChunk<'t>,
     ) -> (Vec<RenderChunk<'s>>, Gap) {
-        let full_content_width = self.content.width();
         if full_content_width <= self.width {
             // Entire content fits.
             return (content.collect(), Gap(self.width - full_content_width));
@@ -109,14 +109,18 @@ where
     }
 
     fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        // The content may be narrower than it would like to be, if it adapts
+        // to the available width.
+        let content: Vec<_> = self.content.render_in(self.width).collect();
+        let content_width = content.iter().map(|chunk| chunk.width).sum();
         let (result, gap) = match self.truncate {
             Direction::Left => {
                 let (mut result, gap) =
-                    self.render_impl(self.content.render().rev(), truncate_start);
+                    self.render_impl(content.into_iter().rev(), content_width, truncate_start);
                 result.reverse();
                 (result, gap)
             }
-            Direction::Right => self.render_impl(self.content.render(), truncate_end),
+            Direction::Right => self.render_impl(content.into_iter(), content_width, truncate_end),
         };
         let mut result = VecDeque::from(result);
 
@@ -133,7 +137,7 @@ where
     }
 }
 
-fn truncate_end<'s>(input: RenderChunk<'s>, target: usize) -> RenderChunk<'s> {
+pub(crate) fn truncate_end<'s>(input: RenderChunk<'s>, target: usize) -> RenderChunk<'s> {
     let mut best_index = 0;
     let mut best_width = 0;
 
@@ -181,7 +185,7 @@ pub enum Direction {
 
 #[cfg(test)]
 mod tests {
-    use crate::element::{Cursor, IntoElement, Text};
+    use crate::element::{Abbreviated, Cursor, IntoElement, Text};
 
     use super::*;
 
@@ -213,6 +217,17 @@ mod tests {
         assert_eq!(render, ["", "     "].map(RenderChunk::from));
     }
 
+    #[test]
+    fn adaptive_content() {
+        let element = Abbreviated("hello", "hi").fixed_width(4);
+        let render: Vec<_> = element.render().collect();
+        assert_eq!(render, ["hi", "  "].map(RenderChunk::from));
+
+        let element = Abbreviated("hello", "hi").fixed_width(5);
+        let render: Vec<_> = element.render().collect();
+        assert_eq!(render, [RenderChunk::from("hello")]);
+    }
+
     #[test]
     fn short_content() {
         let element = "foo".fixed_width(6);
@@ -5,6 +5,19 @@
 use crate::element::Element;
 use crate::render::RenderChunk;
 
+/// Renders an element that follows others in a sequence, within the width
+/// left over by the previous elements.
+fn render_next<'s, E: Element<'s> + ?Sized>(
+    element: &E,
+    max_width: &mut usize,
+    result: &mut Vec<RenderChunk<'s>>,
+) {
+    for chunk in element.render_in(*max_width) {
+        *max_width = max_width.saturating_sub(chunk.width);
+        result.push(chunk);
+    }
+}
+
 impl<'s, E: Element<'s> + ?Sized> Element<'s> for &'_ E {
     fn width(&self) -> usize {
         (*self).width()
@@ -13,6 +26,10 @@ impl<'s, E: Element<'s> + ?Sized> Element<'s> for &'_ E {
     fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
         (*self).render()
     }
+
+    fn render_in(&self, max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        (*self).render_in(max_width)
+    }
 }
 
 impl<'s, E: Element<'s>> Element<'s> for [E] {
@@ -23,6 +40,14 @@ impl<'s, E: Element<'s>> Element<'s> for [E] {
     fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
         self.iter().flat_map(|e| e.render())
     }
+
+    fn render_in(&self, mut max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        let mut result = Vec::new();
+        for e in self {
+            render_next(e, &mut max_width, &mut result);
+        }
+        result.into_iter()
+    }
 }
 
 impl<'s, E: Element<'s>, const N: usize> Element<'s> for [E; N] {
@@ -33,6 +58,14 @@ impl<'s, E: Element<'s>, const N: usize> Element<'s> for [E; N] {
     fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
         self.iter().flat_map(|e| e.render())
     }
+
+    fn render_in(&self, mut max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        let mut result = Vec::new();
+        for e in self {
+            render_next(e, &mut max_width, &mut result);
+        }
+        result.into_iter()
+    }
 }
 
 impl<'s, E: Element<'s>> Element<'s> for Option<E> {
@@ -46,6 +79,10 @@ impl<'s, E: Element<'s>> Element<'s> for Option<E> {
     fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
         self.iter().flat_map(|e| e.render())
     }
+
+    fn render_in(&self, max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        self.iter().flat_map(move |e| e.render_in(max_width))
+    }
 }
 
 impl<'s> Element<'s> for () {
@@ -74,6 +111,16 @@ macro_rules! impl_element_for_tuple {
                 self.0.render()
                 $(.chain(self.$n.render()))*
             }
+
+            fn render_in(
+                &self,
+                mut max_width: usize,
+            ) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+                let mut result = Vec::new();
+                render_next(&self.0, &mut max_width, &mut result);
+                $(render_next(&self.$n, &mut max_width, &mut result);)*
+                result.into_iter()
+            }
         }
     };
 }
@@ -98,11 +145,16 @@ impl<'s, L: Element<'s>, R: Element<'s>> Element<'s> for either::Either<L, R> {
     fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
         self.as_ref().map_either(L::render, R::render)
     }
+
+    fn render_in(&self, max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        self.as_ref()
+            .map_either(|l| l.render_in(max_width), |r| r.render_in(max_width))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::element::{Gap, IntoElement};
+    use crate::element::{Abbreviated, Gap, IntoElement, Text};
 
     use super::*;
 
@@ -126,6 +178,21 @@ mod tests {
         is_element::<either::Either<Gap, (Gap, Gap)>>();
     }
 
+    #[test]
+    fn render_in_sequence() {
+        let element = (
+            Text::from("foo"),
+            Abbreviated("hello", "hi"),
+            Abbreviated("world", "w"),
+        );
+        let render: Vec<_> = element.render_in(10).collect();
+        assert_eq!(render, ["foo", "hello", "w"].map(RenderChunk::from));
+
+        let element = [Abbreviated("hello", "hi"), Abbreviated("world", "w")];
+        let render: Vec<_> = element.render_in(7).collect();
+        assert_eq!(render, ["hello", "w"].map(RenderChunk::from));
+    }
+
     fn same_lifetime<'s, L: Element<'s>, R: Element<'s>>(_: &L, _: &R) {}
 
     #[test]
@@ -56,7 +56,7 @@ mod tests {
         let string = "foo".to_owned();
         let not_static = string[..].into_element();
 
-        let mut r = Renderer::new(vec![]);
+        let mut r = Renderer::for_test(vec![]);
         let _ = r.render(&not_static);
         let _ = r.render(not_static.fixed_width(42));
         let _ = r.finish();
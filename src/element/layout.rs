@@ -0,0 +1,314 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+use crate::element::{BoxElement, Element, FixedWidth, Gap, IntoElement};
+use crate::render::RenderChunk;
+
+/// How the width of a child of a [`Layout`] is determined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// Exactly the given width. The child is padded or truncated to fit.
+    ///
+    /// If the children of the layout do not fit, this child keeps its width
+    /// unless nothing else can be truncated.
+    Fixed(usize),
+    /// The width of the child, but no less than the given width.
+    Min(usize),
+    /// The width of the child, but no more than the given width.
+    Max(usize),
+    /// The width of the child, plus a share of any remaining space in
+    /// proportion to the given weight.
+    Flex(usize),
+    /// Only a share of the remaining space, as if it were
+    /// [`Flex(1)`](Self::Flex) with no content. This is useful for spacers.
+    Fill,
+}
+
+/// A child of a [`Layout`].
+struct Child<'s> {
+    /// The width that the element would like to have.
+    width: usize,
+    /// Renders the element within the width it is given.
+    render_in: Box<dyn Fn(usize) -> BoxElement<'s> + 's>,
+    constraint: Constraint,
+    priority: i32,
+}
+
+impl Child<'_> {
+    /// Returns the width of the child before any space is distributed.
+    fn base_width(&self) -> usize {
+        let width = self.width;
+        match self.constraint {
+            Constraint::Fixed(fixed) => fixed,
+            Constraint::Min(min) => width.max(min),
+            Constraint::Max(max) => width.min(max),
+            Constraint::Flex(_) => width,
+            Constraint::Fill => 0,
+        }
+    }
+
+    /// Returns the width that the child should not be truncated below.
+    fn min_width(&self) -> usize {
+        match self.constraint {
+            Constraint::Fixed(fixed) => fixed,
+            Constraint::Min(min) => min,
+            _ => 0,
+        }
+    }
+
+    /// Returns the weight with which the child grows.
+    fn weight(&self) -> usize {
+        match self.constraint {
+            Constraint::Flex(weight) => weight,
+            Constraint::Fill => 1,
+            _ => 0,
+        }
+    }
+}
+
+/// An element that arranges its children horizontally within a given width.
+///
+/// Each child has a [`Constraint`] that determines its width. Any space left
+/// over is distributed among the children with [`Constraint::Flex`] and
+/// [`Constraint::Fill`], or added to the end if there are none.
+///
+/// If the children do not fit, the children with the lowest priority are
+/// truncated first, down to their minimum or fixed width. Children have a
+/// priority of 0 unless added with [`with_priority`](Self::with_priority).
+/// Among children with the same priority, the rightmost is truncated first.
+///
+/// # Example
+///
+/// ```
+/// use line_ui::element::{Constraint, Element, Layout};
+///
+/// let line = Layout::new(20)
+///     .with("left", Constraint::Max(10))
+///     .with((), Constraint::Fill)
+///     .with_priority("right", Constraint::Fixed(5), 1);
+/// assert_eq!(line.width(), 20);
+/// ```
+pub struct Layout<'s> {
+    width: usize,
+    children: Vec<Child<'s>>,
+}
+
+impl<'s> Layout<'s> {
+    /// Creates a new, empty [`Layout`] with the given width.
+    pub fn new(width: usize) -> Self {
+        Layout {
+            width,
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a child to the right of the layout.
+    ///
+    /// The child is rendered with [`render_in`](Element::render_in), given
+    /// the width that it is allocated.
+    pub fn with<E>(self, element: E, constraint: Constraint) -> Self
+    where
+        E: IntoElement<'s>,
+        E::ElementType: 's,
+    {
+        self.with_priority(element, constraint, 0)
+    }
+
+    /// Adds a child to the right of the layout, with the given priority.
+    /// Children with a lower priority are truncated first.
+    pub fn with_priority<E>(mut self, element: E, constraint: Constraint, priority: i32) -> Self
+    where
+        E: IntoElement<'s>,
+        E::ElementType: 's,
+    {
+        let element = element.into_element();
+        self.children.push(Child {
+            width: element.width(),
+            render_in: Box::new(move |max_width| BoxElement::new_in(&element, max_width)),
+            constraint,
+            priority,
+        });
+        self
+    }
+
+    /// Returns the width of each child.
+    fn widths(&self) -> Vec<usize> {
+        let mut widths: Vec<_> = self.children.iter().map(Child::base_width).collect();
+        let total: usize = widths.iter().sum();
+        if total > self.width {
+            self.shrink(&mut widths, total - self.width);
+        } else {
+            self.grow(&mut widths, self.width - total);
+        }
+        widths
+    }
+
+    /// Truncates the children with the lowest priority, until the given
+    /// number of columns have been removed.
+    fn shrink(&self, widths: &mut [usize], mut excess: usize) {
+        let mut order: Vec<_> = (0..self.children.len()).collect();
+        order.sort_by_key(|&index| (self.children[index].priority, usize::MAX - index));
+        // If the minimum widths do not fit either, they are ignored.
+        for respect_min in [true, false] {
+            for &index in &order {
+                let min = if respect_min {
+                    self.children[index].min_width()
+                } else {
+                    0
+                };
+                let removed = excess.min(widths[index].saturating_sub(min));
+                widths[index] -= removed;
+                excess -= removed;
+            }
+        }
+    }
+
+    /// Distributes the given number of columns among the children that grow.
+    fn grow(&self, widths: &mut [usize], remaining: usize) {
+        let total_weight: usize = self.children.iter().map(Child::weight).sum();
+        if total_weight == 0 {
+            return;
+        }
+        let mut distributed = 0;
+        for (width, child) in widths.iter_mut().zip(&self.children) {
+            let share = remaining * child.weight() / total_weight;
+            *width += share;
+            distributed += share;
+        }
+        // Give the columns lost to rounding to the first children that grow.
+        let growing = widths.iter_mut().zip(&self.children);
+        for (width, _) in growing.filter(|(_, child)| child.weight() != 0) {
+            if distributed == remaining {
+                break;
+            }
+            *width += 1;
+            distributed += 1;
+        }
+    }
+}
+
+impl<'s> Element<'s> for Layout<'s> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        let widths = self.widths();
+        let mut chunks = Vec::new();
+        for (child, &width) in self.children.iter().zip(&widths) {
+            chunks.extend(FixedWidth::new(width, (child.render_in)(width)).render());
+        }
+        let used: usize = widths.iter().sum();
+        chunks.extend(Gap(self.width.saturating_sub(used)).render());
+        chunks.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::Abbreviated;
+
+    use super::*;
+
+    fn text(layout: &Layout) -> String {
+        let chunks: Vec<_> = layout.render().collect();
+        assert_eq!(
+            chunks.iter().map(|chunk| chunk.width).sum::<usize>(),
+            layout.width()
+        );
+        chunks.iter().map(|chunk| chunk.value).collect()
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(text(&Layout::new(0)), "");
+        assert_eq!(text(&Layout::new(3)), "   ");
+    }
+
+    #[test]
+    fn fill() {
+        let layout = Layout::new(12)
+            .with("left", Constraint::Flex(0))
+            .with((), Constraint::Fill)
+            .with("right", Constraint::Flex(0));
+        assert_eq!(text(&layout), "left   right");
+    }
+
+    #[test]
+    fn flex_weights() {
+        let layout = Layout::new(10)
+            .with("a", Constraint::Flex(1))
+            .with("b", Constraint::Flex(2));
+        // 8 columns remain: 2 go to "a" and 5 to "b", and the column lost to
+        // rounding goes to "a".
+        assert_eq!(text(&layout), "a   b     ");
+    }
+
+    #[test]
+    fn fixed_min_max() {
+        let layout = Layout::new(12)
+            .with("abcdef", Constraint::Fixed(4))
+            .with("ab", Constraint::Min(4))
+            .with("abcdef", Constraint::Max(3));
+        assert_eq!(text(&layout), "abcdab  abc ");
+    }
+
+    #[test]
+    fn adaptive_children() {
+        let layout = Layout::new(8)
+            .with(Abbreviated("hello", "hi"), Constraint::Max(3))
+            .with((), Constraint::Fill)
+            .with(Abbreviated("world", "wo"), Constraint::Flex(0));
+        assert_eq!(text(&layout), "hi world");
+    }
+
+    #[test]
+    fn truncate_lowest_priority() {
+        let layout = Layout::new(10)
+            .with_priority("important", Constraint::Flex(0), 1)
+            .with("spam", Constraint::Flex(0))
+            .with("eggs", Constraint::Flex(0));
+        assert_eq!(text(&layout), "importants");
+
+        let layout = Layout::new(12)
+            .with("spam", Constraint::Flex(0))
+            .with_priority("important", Constraint::Flex(0), 1);
+        assert_eq!(text(&layout), "spaimportant");
+    }
+
+    #[test]
+    fn truncate_rightmost_first() {
+        let layout = Layout::new(6)
+            .with("spam", Constraint::Flex(0))
+            .with("eggs", Constraint::Flex(0));
+        assert_eq!(text(&layout), "spameg");
+    }
+
+    #[test]
+    fn truncate_to_min() {
+        let layout = Layout::new(6)
+            .with("spam", Constraint::Flex(0))
+            .with("eggs", Constraint::Min(3));
+        assert_eq!(text(&layout), "spaegg");
+
+        // The minimum width is ignored if nothing else can be truncated.
+        let layout = Layout::new(2).with("eggs", Constraint::Min(3));
+        assert_eq!(text(&layout), "eg");
+    }
+
+    #[test]
+    fn truncate_fixed() {
+        let layout = Layout::new(6)
+            .with("spam", Constraint::Flex(0))
+            .with("eggs", Constraint::Fixed(4));
+        assert_eq!(text(&layout), "speggs");
+
+        // Like minimum widths, fixed widths are ignored if nothing else can be
+        // truncated.
+        let layout = Layout::new(5)
+            .with("abc", Constraint::Fixed(4))
+            .with("xyz", Constraint::Fixed(4));
+        assert_eq!(text(&layout), "abc x");
+    }
+}
@@ -30,6 +30,13 @@ impl<'s, E: Element<'s>> Element<'s> for Styled<E> {
             item
         })
     }
+
+    fn render_in(&self, max_width: usize) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        self.inner.render_in(max_width).map(|mut item| {
+            item.style = item.style.or(self.style);
+            item
+        })
+    }
 }
 
 #[cfg(test)]
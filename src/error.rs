@@ -0,0 +1,73 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+use std::{fmt, io};
+
+/// An error returned by a [`Renderer`](crate::Renderer).
+///
+/// An `Error` can be converted into an [`io::Error`], so the `?` operator can
+/// be used in functions that return [`io::Result`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// An error occurred while writing to the terminal.
+    Io(io::Error),
+    /// A frame is being rendered, so [`finish`](crate::Renderer::finish) must
+    /// be called before the method that returned this error.
+    Unfinished,
+    /// A frame has more lines than can be displayed.
+    TooManyLines,
+}
+
+/// A specialized [`Result`](std::result::Result) type for rendering.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => err.fmt(f),
+            Error::Unfinished => f.write_str("finish() must be called after rendering"),
+            Error::TooManyLines => write!(f, "a frame cannot have more than {} lines", u16::MAX),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::Io(err) => err,
+            err => io::Error::other(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_io_error() {
+        let err = io::Error::from(Error::Io(io::Error::other("oops")));
+        assert_eq!(err.to_string(), "oops");
+
+        let err = io::Error::from(Error::Unfinished);
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(err.to_string(), "finish() must be called after rendering");
+    }
+}
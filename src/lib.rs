@@ -1,11 +1,20 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
 
+pub mod block;
 pub mod element;
+mod error;
 mod render;
+pub mod restore;
+pub mod session;
+pub mod shared;
+#[cfg(feature = "signals")]
+pub mod signal;
 mod style;
+pub mod testing;
 
-pub use render::Renderer;
+pub use error::{Error, Result};
+pub use render::{Frame, Mode, Renderer};
 pub use style::*;
 
 #[cfg(feature = "unicode")]
@@ -31,7 +40,7 @@ mod tests {
 
     #[test]
     fn styled() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
+        let mut r = Renderer::for_test(vec![]);
         r.reset()?
             .render((
                 "one".into_element(),
@@ -41,14 +50,14 @@ mod tests {
             .finish()?;
         assert_eq!(
             r.writer,
-            b"\rone\x1b[m\x1b[38;5;4m\x1b[48;5;5mtwo\x1b[mthree\x1b[m\x1b[K\x1b[?25l",
+            b"\rone\x1b[38;5;4;48;5;5mtwo\x1b[mthree\x1b[K\x1b[?25l",
         );
         Ok(())
     }
 
     #[test]
     fn styledd_fixed_width() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
+        let mut r = Renderer::for_test(vec![]);
         r.reset()?
             .render(
                 "test"
@@ -58,7 +67,7 @@ mod tests {
             .finish()?;
         assert_eq!(
             r.writer,
-            b"\r\x1b[38;5;42m\x1b[48;5;43mtest\x1b[m\x1b[38;5;42m\x1b[48;5;43m      \x1b[m\x1b[K\x1b[?25l",
+            b"\r\x1b[38;5;42;48;5;43mtest      \x1b[m\x1b[K\x1b[?25l",
         );
         Ok(())
     }
@@ -2,13 +2,23 @@
  * Copyright (c) 2025 Jasmine Tai. All rights reserved.
  */
 
+mod frame;
+
 use std::io::{self, Write};
+use std::ops::Range;
+use std::os::fd::AsFd;
+use std::time::{Duration, Instant};
+
+use termion::cursor::DetectCursorPos;
+use termion::screen::{ToAlternateScreen, ToMainScreen};
+use termion::{clear, cursor, style};
 
-use termion::style::Reset;
-use termion::{clear, cursor};
+use crate::block::Block;
+use crate::element::{CursorShape, Element, IntoElement, SetCursorShape, truncate_end};
+use crate::session::RawMode;
+use crate::{Error, Result, Style, restore};
 
-use crate::Style;
-use crate::element::Element;
+pub use frame::*;
 
 /// A chunk of text with a constant style to be rendered.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,6 +33,9 @@ pub struct RenderChunk<'s> {
     /// true, then `value` must be `""`, `width` must be `0`, and `style` must
     /// be `Style::EMPTY`.
     pub(crate) cursor: bool,
+    /// The shape of the cursor, or `None` for the terminal's default shape.
+    /// This must be `None` unless `cursor` is true.
+    pub(crate) cursor_shape: Option<CursorShape>,
 }
 
 impl<'s> RenderChunk<'s> {
@@ -31,8 +44,16 @@ impl<'s> RenderChunk<'s> {
         width: 0,
         style: Style::EMPTY,
         cursor: true,
+        cursor_shape: None,
     };
 
+    pub const fn shaped_cursor(shape: CursorShape) -> RenderChunk<'static> {
+        RenderChunk {
+            cursor_shape: Some(shape),
+            ..RenderChunk::CURSOR
+        }
+    }
+
     pub fn new(value: &'s str, style: Style) -> Self {
         RenderChunk::with_known_width(value, crate::width(value), style)
     }
@@ -44,6 +65,7 @@ impl<'s> RenderChunk<'s> {
             width,
             style,
             cursor: false,
+            cursor_shape: None,
         }
     }
 }
@@ -54,266 +76,2587 @@ impl<'s> From<&'s str> for RenderChunk<'s> {
     }
 }
 
+/// A chunk of a [`Line`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LineChunk {
+    value: String,
+    width: usize,
+    style: Style,
+}
+
+/// A line of text that owns its contents.
+///
+/// The `Renderer` retains the lines that have been written to the terminal,
+/// so that unchanged lines can be skipped when the next frame is rendered.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Line {
+    chunks: Vec<LineChunk>,
+    width: usize,
+}
+
+impl Line {
+    /// Copies the contents of an element into a new line. Any cursors in the
+    /// element are discarded.
+    pub(crate) fn new<'s, E: Element<'s>>(element: E) -> Self {
+        let mut line = Line::default();
+        for chunk in element.render().filter(|chunk| !chunk.cursor) {
+            line.push(chunk);
+        }
+        line
+    }
+
+    fn push(&mut self, chunk: RenderChunk) {
+        self.chunks.push(LineChunk {
+            value: chunk.value.to_owned(),
+            width: chunk.width,
+            style: chunk.style,
+        });
+        self.width += chunk.width;
+    }
+
+    /// Returns a copy of this line, truncated to the given width.
+    fn truncated(&self, width: usize) -> Line {
+        let mut line = Line::default();
+        for chunk in self.render() {
+            let available_width = width - line.width;
+            if chunk.width <= available_width {
+                line.push(chunk);
+            } else {
+                if available_width > 0 {
+                    line.push(truncate_end(chunk, available_width));
+                }
+                break;
+            }
+        }
+        line
+    }
+}
+
+impl<'s> Element<'s> for &'s Line {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn render(&self) -> impl DoubleEndedIterator<Item = RenderChunk<'s>> {
+        self.chunks
+            .iter()
+            .map(|chunk| RenderChunk::with_known_width(&chunk.value, chunk.width, chunk.style))
+    }
+}
+
+/// The way in which a [`Renderer`] displays its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum Mode {
+    /// Frames are drawn starting at the cursor position, and redrawn in place.
+    #[default]
+    Inline,
+    /// Plain text is written, without any styles or escape sequences. This is
+    /// suitable when the output is not a terminal, e.g., a file or a pipe.
+    ///
+    /// In this mode, frames are not drawn as they are rendered. Only
+    /// [printed](Renderer::print) lines and frames that are
+    /// [left](Renderer::leave) are written.
+    Plain,
+    /// Frames are drawn on the terminal's alternate screen, starting from the
+    /// top, so that the terminal's history is left untouched.
+    ///
+    /// The alternate screen is entered when the first frame is drawn, and the
+    /// main screen is restored by [`clear`](Renderer::clear). Lines that are
+    /// [printed](Renderer::print) are written to the main screen once it is
+    /// restored. If the renderer is [left](Renderer::leave), the most recent
+    /// frame is also written to the main screen.
+    FullScreen,
+    /// Frames are drawn in a status region, which occupies the given number
+    /// of rows at the bottom of the terminal. The rest of the terminal is
+    /// made into a scroll region, so that ordinary output scrolls above the
+    /// status region without disturbing it.
+    ///
+    /// The scroll region is set up when the first frame is drawn, and removed
+    /// by [`clear`](Renderer::clear). [Printed](Renderer::print) lines are
+    /// written at the cursor position within the scroll region. Since the
+    /// cursor is always returned to the scroll region, any
+    /// [`Cursor`](crate::element::Cursor)s in the rendered lines are ignored.
+    ///
+    /// At least one row is always left for the scroll region. If the size of
+    /// the terminal is unknown, nothing is drawn.
+    StatusBar {
+        /// The number of rows to reserve for the status region.
+        rows: u16,
+    },
+}
+
+/// Saves the cursor position (DECSC).
+const SAVE_CURSOR: &str = "\x1b7";
+/// Restores the cursor position saved by [`SAVE_CURSOR`] (DECRC).
+const RESTORE_CURSOR: &str = "\x1b8";
+/// Removes the scroll region (DECSTBM), so that the whole screen scrolls.
+const RESET_SCROLL_REGION: &str = "\x1b[r";
+
+/// A function that returns the size of the terminal, as `(columns, rows)`, or
+/// `None` if the size is unknown.
+type SizeSource = Box<dyn FnMut() -> Option<(u16, u16)> + Send>;
+
+/// Queries the size of the terminal. A size with no columns or no rows, such
+/// as that of a pseudoterminal whose size has not been set, is unknown.
+fn query_size(size_source: &mut SizeSource) -> Option<(u16, u16)> {
+    size_source().filter(|&(columns, rows)| columns != 0 && rows != 0)
+}
+
 /// A struct that outputs lines to a [writer](Write).
+///
+/// The `Renderer` remembers the lines of the previous frame, and only
+/// rewrites the lines that have changed.
+///
+/// Lines that are wider than the terminal are truncated, so that each line
+/// occupies exactly one row of the terminal. By default, the size of the
+/// terminal is queried using [`termion::terminal_size`]; this can be changed
+/// with [`with_size_source`](Self::with_size_source). If a frame has more
+/// lines than the terminal has rows, only the lines within a
+/// [viewport](Self::scroll_to) are displayed.
+///
+/// Optionally, a minimum interval between frames can be set with
+/// [`with_min_interval`](Self::with_min_interval). Frames that are finished
+/// too soon after the previous one are not drawn immediately; only the most
+/// recent frame is drawn once the interval has elapsed.
+///
+/// In [`Mode::Inline`], the first line can start partway through a row of
+/// the terminal, e.g., after a prompt; see
+/// [`with_start_column`](Self::with_start_column).
+///
+/// The output of each frame is buffered, and written to the writer all at
+/// once. The frame can additionally be wrapped in a synchronized update with
+/// [`with_synchronized_output`](Self::with_synchronized_output).
 pub struct Renderer<W: Write> {
     pub(crate) writer: W,
-    lines_rendered: u16,
+    /// Output that has yet to be written to `writer`.
+    buffer: Vec<u8>,
+    synchronized_output: bool,
+    pub(crate) mode: Mode,
+    size_source: SizeSource,
+    /// The size of the terminal as of the last call to `reset`.
+    pub(crate) size: Option<(u16, u16)>,
+    /// The lines currently displayed on the terminal.
+    screen: Vec<Line>,
+    /// The lines of the frame being rendered, or of the most recent frame.
+    frame: Vec<Line>,
     desired_cursor: Option<(u16, u16)>,
-    is_dirty: bool, // flag for debugging
+    desired_cursor_shape: Option<CursorShape>,
+    /// Whether `frame` is complete, but has yet to be drawn.
+    pending: bool,
+    min_interval: Duration,
+    /// The column of the terminal at which the first line starts.
+    start_column: u16,
+    /// The index of the first visible line of the frame.
+    scroll_offset: usize,
+    follow_cursor: bool,
+    last_draw: Option<Instant>,
+    /// The actual position of the terminal cursor, relative to the start of
+    /// the first line. The column is `None` if it is unknown.
+    cursor: (u16, Option<u16>),
+    /// Whether the terminal cursor is shown, or `None` if it is unknown.
+    cursor_visible: Option<bool>,
+    /// The shape of the terminal cursor, or `None` if it has not been
+    /// changed from the default.
+    cursor_shape: Option<CursorShape>,
+    /// Whether a frame is being rendered, i.e., `render` has been called
+    /// since the last call to `finish`.
+    is_dirty: bool,
+    /// Whether the alternate screen has been entered.
+    alternate_screen: bool,
+    /// Lines printed in full-screen mode, which are written to the main
+    /// screen once it is restored.
+    deferred: Vec<Line>,
+    /// The first row of the status region, if the scroll region has been set
+    /// up in [`Mode::StatusBar`].
+    status_top: Option<u16>,
+    /// The identifier used to register how to [restore](crate::restore) the
+    /// terminal, if the renderer has been registered.
+    pub(crate) restore_id: Option<u64>,
 }
 
 impl<W: Write> Renderer<W> {
     /// Creates a new [`Renderer`] that writes to the given writer.
+    ///
+    /// By default, the size of the terminal is that of standard output,
+    /// regardless of the writer. If the writer is not standard output, use
+    /// [`with_size_source`](Self::with_size_source) or
+    /// [`with_unknown_size`](Self::with_unknown_size).
     pub fn new(writer: W) -> Self {
+        let mut size_source: SizeSource = Box::new(|| termion::terminal_size().ok());
         Renderer {
             writer,
-            lines_rendered: 0,
+            buffer: Vec::new(),
+            synchronized_output: false,
+            mode: Mode::Inline,
+            size: query_size(&mut size_source),
+            size_source,
+            screen: Vec::new(),
+            frame: Vec::new(),
             desired_cursor: None,
+            desired_cursor_shape: None,
+            pending: false,
+            min_interval: Duration::ZERO,
+            start_column: 0,
+            scroll_offset: 0,
+            follow_cursor: true,
+            last_draw: None,
+            cursor: (0, None),
+            cursor_visible: None,
+            cursor_shape: None,
             is_dirty: false,
+            alternate_screen: false,
+            deferred: Vec::new(),
+            status_top: None,
+            restore_id: None,
         }
     }
 
-    /// Resets the renderer's state.
-    fn reset_state(&mut self) {
-        self.lines_rendered = 0;
-        self.desired_cursor = None;
-        self.is_dirty = false;
+    /// Changes the function used to determine the size of the terminal.
+    ///
+    /// The function should return the size as `(columns, rows)`, or `None` if
+    /// the size is unknown, in which case lines are never truncated. A size
+    /// with no columns or no rows is also treated as unknown. The function is
+    /// called at the start of each frame.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use line_ui::Renderer;
+    ///
+    /// let r = Renderer::new(vec![]).with_size_source(|| Some((80, 24)));
+    /// # drop(r);
+    /// ```
+    pub fn with_size_source<F>(mut self, source: F) -> Self
+    where
+        F: FnMut() -> Option<(u16, u16)> + Send + 'static,
+    {
+        self.size_source = Box::new(source);
+        self.size = query_size(&mut self.size_source);
+        self
     }
 
-    /// Resets the cursor position, allowing rendering to start over.
-    pub fn reset(&mut self) -> io::Result<&mut Self> {
-        assert!(!self.is_dirty, "finish() must be called after rendering");
-        // Reset the cursor to the top-left.
-        let current_cursor_line = match self.desired_cursor {
-            // If there's a desired cursor position, the cursor is there.
-            Some((line, _)) => line,
-            // Otherwise, it's the last line rendered.
-            None => self.lines_rendered.saturating_sub(1),
-        };
-        if current_cursor_line != 0 {
-            write!(self.writer, "{}", cursor::Up(current_cursor_line))?;
-        }
-        write!(self.writer, "\r")?;
+    /// Treats the size of the terminal as unknown, so that lines are never
+    /// truncated. This is useful if the writer is not a terminal, e.g., in
+    /// tests.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use line_ui::Renderer;
+    ///
+    /// let r = Renderer::new(vec![]).with_unknown_size();
+    /// # drop(r);
+    /// ```
+    pub fn with_unknown_size(self) -> Self {
+        self.with_size_source(|| None)
+    }
 
-        self.reset_state();
-        Ok(self)
+    /// Creates a new [`Renderer`] whose size is unknown, so that the output
+    /// does not depend on the terminal in which the tests are run.
+    #[cfg(test)]
+    pub(crate) fn for_test(writer: W) -> Self {
+        Renderer::new(writer).with_unknown_size()
     }
 
-    /// Clears the UI, resetting the terminal back to its initial state.
+    /// Sets the [`Mode`] of the renderer.
     ///
-    /// Note that this method is automatically called when the `Renderer` is
-    /// [dropped](Drop).
-    pub fn clear(&mut self) -> io::Result<()> {
-        assert!(!self.is_dirty, "finish() must be called after rendering");
-        self.reset()?;
-        write!(self.writer, "{}{}", clear::AfterCursor, cursor::Show)
+    /// This should be called before anything is rendered.
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
     }
 
-    /// Renders a line.
-    pub fn render<'s, E: Element<'s>>(&mut self, line: E) -> io::Result<&mut Self> {
-        self.is_dirty = true;
-        // If this isn't the first line, then move to the next line.
-        if self.lines_rendered != 0 {
-            write!(self.writer, "\n\r")?;
+    /// Switches to [`Mode::Plain`] if the writer is not a terminal.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use line_ui::Renderer;
+    ///
+    /// // Works both interactively and when piped to a file.
+    /// let r = Renderer::new(std::io::stdout()).detect_tty();
+    /// # drop(r);
+    /// ```
+    pub fn detect_tty(self) -> Self
+    where
+        W: AsFd,
+    {
+        if termion::is_tty(&self.writer.as_fd()) {
+            self
+        } else {
+            self.with_mode(Mode::Plain)
         }
-        // Render each chunk.
-        let mut column = 0;
-        for chunk in line.render() {
-            if chunk.cursor {
-                debug_assert_eq!(chunk.value, "");
-                debug_assert_eq!(chunk.width, 0);
-                self.desired_cursor = Some((self.lines_rendered, column as u16));
-            } else {
-                write!(self.writer, "{}{}{Reset}", chunk.style, chunk.value)?;
-                column += chunk.width;
-            }
+    }
+
+    /// Registers the renderer to be cleaned up by
+    /// [`restore_terminal`](restore::restore_terminal), e.g., when the program
+    /// panics. See the [`restore`] module.
+    ///
+    /// The file descriptor of the writer is duplicated, so that the output
+    /// that restores the terminal is written to the same terminal as the
+    /// renderer.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use line_ui::Renderer;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// line_ui::restore::install_panic_hook();
+    /// let r = Renderer::new(std::io::stderr()).with_restore()?;
+    /// # drop(r);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_restore(mut self) -> io::Result<Self>
+    where
+        W: AsFd,
+    {
+        if self.restore_id.is_none() {
+            self.restore_id = Some(restore::register(self.writer.as_fd())?);
         }
-        write!(self.writer, "{}", clear::UntilNewline)?;
-        self.lines_rendered += 1;
         Ok(self)
     }
 
-    /// Finishes rendering. This should be called immediately after the
-    /// [`render`](Self::render) calls are complete.
-    pub fn finish(&mut self) -> io::Result<()> {
+    /// Sets the minimum interval between drawing frames.
+    ///
+    /// If a frame is [finished](Self::finish) before the interval has elapsed
+    /// since the previous frame was drawn, it is not drawn immediately, and is
+    /// instead replaced by the next frame. A pending frame can be drawn at any
+    /// time with [`flush`](Self::flush), and is drawn automatically by
+    /// [`clear`](Self::clear), [`leave`](Self::leave), and
+    /// [`print`](Self::print).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use line_ui::Renderer;
+    ///
+    /// // Draw at most 30 frames per second.
+    /// let r = Renderer::new(vec![]).with_min_interval(Duration::from_secs(1) / 30);
+    /// # drop(r);
+    /// ```
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// Sets the column of the terminal at which the first line starts, so
+    /// that any text before it on the same row is left untouched. This
+    /// applies to [`Mode::Inline`] and [`Mode::StatusBar`].
+    ///
+    /// In [`Mode::StatusBar`], the start column is instead the column of the
+    /// cursor in the scroll region. If it is not 0, the first
+    /// [printed](Self::print) or [left](Self::leave) line starts on a new row.
+    ///
+    /// The first line is truncated to fit in the rest of the row. If the
+    /// terminal becomes narrower than the start column, the text before the
+    /// first line is assumed to have been wrapped onto multiple rows.
+    /// [Printed](Self::print) and [left](Self::leave) lines continue the row
+    /// that the first line started on, after which lines start at column 0.
+    ///
+    /// The start column can be queried from the terminal with
+    /// [`detect_start_column`](Self::detect_start_column).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use line_ui::Renderer;
+    /// use line_ui::element::IntoElement;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut r = Renderer::new(vec![]).with_start_column(2);
+    /// r.writer_mut().extend_from_slice(b"$ ");
+    /// r.reset()?.render("ls".into_element())?.finish()?;
+    /// assert!(r.writer().starts_with(b"$ \r\x1b[2Cls"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_start_column(mut self, column: u16) -> Self {
+        self.start_column = column;
+        self
+    }
+
+    /// Sets the start column to the column of the terminal cursor, which is
+    /// queried using a cursor position report (`CSI 6 n`). See
+    /// [`with_start_column`](Self::with_start_column) for details.
+    ///
+    /// The response is read from standard input, which must be a terminal in
+    /// raw mode. This should be called while nothing is displayed, i.e.,
+    /// before the first frame is drawn, or after [`clear`](Self::clear).
+    ///
+    /// Returns [`Error::Unfinished`] if a frame is being rendered, or
+    /// [`Error::Io`] if the terminal does not respond.
+    pub fn detect_start_column(&mut self) -> Result<u16> {
+        self.flush()?;
+        let (column, _) = self.writer.cursor_pos()?;
+        self.start_column = column.saturating_sub(1);
+        Ok(self.start_column)
+    }
+
+    /// Sets whether the viewport is scrolled to keep the
+    /// [`Cursor`](crate::element::Cursor) visible. This is enabled by default.
+    ///
+    /// See [`scroll_to`](Self::scroll_to) for details.
+    pub fn with_follow_cursor(mut self, enabled: bool) -> Self {
+        self.follow_cursor = enabled;
+        self
+    }
+
+    /// Scrolls the viewport so that the given line of the frame is at the
+    /// top. This takes effect when the next frame is drawn.
+    ///
+    /// If a frame has more lines than the terminal has rows, only the lines
+    /// within the viewport are displayed. The viewport never extends past the
+    /// last line of the frame. Unless disabled with
+    /// [`with_follow_cursor`](Self::with_follow_cursor), the viewport is
+    /// scrolled by as little as possible so that the line containing the
+    /// cursor is visible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use line_ui::Renderer;
+    /// use line_ui::element::IntoElement;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut r = Renderer::new(vec![]).with_size_source(|| Some((80, 2)));
+    /// r.scroll_to(1);
+    /// r.draw(|frame| {
+    ///     for line in ["one", "two", "three"] {
+    ///         frame.render(line.into_element())?;
+    ///     }
+    ///     Ok(())
+    /// })?;
+    /// assert_eq!(r.scroll_offset(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn scroll_to(&mut self, line: usize) {
+        self.scroll_offset = line;
+    }
+
+    /// Returns the index of the line of the frame at the top of the viewport.
+    /// This is updated whenever a frame is drawn.
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    /// Sets whether each frame is wrapped in a synchronized update.
+    ///
+    /// Terminals that support synchronized updates (`CSI ? 2026 h`) display
+    /// the whole frame at once, rather than displaying each line as it is
+    /// written, which prevents tearing. Other terminals ignore the escape
+    /// sequences.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use line_ui::Renderer;
+    ///
+    /// let r = Renderer::new(vec![]).with_synchronized_output(true);
+    /// # drop(r);
+    /// ```
+    pub fn with_synchronized_output(mut self, enabled: bool) -> Self {
+        self.synchronized_output = enabled;
+        self
+    }
+
+    /// Returns a reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the writer.
+    ///
+    /// Writing to the terminal directly is likely to corrupt the rendered
+    /// lines; use [`print`](Self::print) instead.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Resets the renderer's state.
+    fn reset_state(&mut self) {
+        self.frame.clear();
+        self.desired_cursor = None;
+        self.desired_cursor_shape = None;
+        self.pending = false;
         self.is_dirty = false;
-        if let Some((line, column)) = self.desired_cursor {
-            let up = self.lines_rendered - line - 1;
-            if up != 0 {
-                write!(self.writer, "{}", cursor::Up(up))?;
+    }
+
+    /// Writes the buffered output to the writer in a single write, and flushes
+    /// the writer.
+    fn flush_buffer(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            if self.synchronized_output {
+                self.buffer.splice(0..0, *b"\x1b[?2026h");
+                self.buffer.extend_from_slice(b"\x1b[?2026l");
             }
-            write!(self.writer, "\r")?;
-            if column != 0 {
-                write!(self.writer, "{}", cursor::Right(column))?;
+            let result = self.writer.write_all(&self.buffer);
+            self.buffer.clear();
+            result?;
+            if let Some(id) = self.restore_id
+                && restore::is_enabled()
+            {
+                restore::update(id, self.restore_sequence()?);
             }
-            write!(self.writer, "{}", cursor::Show)?;
-        } else {
-            write!(self.writer, "{}", cursor::Hide)?;
         }
         self.writer.flush()
     }
 
-    /// Leaves the currently-rendered text, making it impossible to clear.
-    ///
-    /// This method may be used if you want to dispose of this `Renderer`
-    /// without clearing the currently-rendered text. This should be called
-    /// after [`finish`](Self::finish).
-    pub fn leave(&mut self) -> io::Result<()> {
-        assert!(!self.is_dirty, "finish() must be called after rendering");
-        if self.lines_rendered == 0 {
-            return Ok(());
+    /// Returns the output that restores the terminal to its initial state,
+    /// like [`clear`](Self::clear), without relying on anything else being
+    /// written first.
+    fn restore_sequence(&self) -> io::Result<Vec<u8>> {
+        let mut sequence = Vec::new();
+        match self.mode {
+            Mode::Plain => return Ok(sequence),
+            Mode::Inline => {
+                if !self.screen.is_empty() {
+                    if self.cursor.0 != 0 {
+                        write!(sequence, "{}", cursor::Up(self.cursor.0))?;
+                    }
+                    write!(sequence, "\r")?;
+                    let start = self.line_start(0);
+                    if start != 0 {
+                        write!(sequence, "{}", cursor::Right(start))?;
+                    }
+                    write!(sequence, "{}", clear::AfterCursor)?;
+                }
+            }
+            Mode::FullScreen => {
+                if self.alternate_screen {
+                    write!(sequence, "{ToMainScreen}")?;
+                }
+            }
+            Mode::StatusBar { .. } => {
+                if let Some(top) = self.status_top {
+                    write!(sequence, "{SAVE_CURSOR}{}", cursor::Goto(1, top + 1))?;
+                    write!(sequence, "{}{RESET_SCROLL_REGION}", clear::AfterCursor)?;
+                    write!(sequence, "{RESTORE_CURSOR}")?;
+                }
+            }
         }
-        let down = match self.desired_cursor {
-            Some((row, _)) => self.lines_rendered - row - 1,
-            None => 0,
-        };
-        if down != 0 {
-            write!(self.writer, "{}", cursor::Down(down))?;
+        if self.cursor_shape.is_some() {
+            write!(sequence, "{}", SetCursorShape(None))?;
         }
-        write!(self.writer, "\n\r")?;
-        self.reset_state();
-        Ok(())
+        write!(sequence, "{}{}", style::Reset, cursor::Show)?;
+        Ok(sequence)
     }
-}
 
-impl<W: Write> Drop for Renderer<W> {
-    fn drop(&mut self) {
-        if !std::thread::panicking() {
-            // If dropping due to panic, don't bother cleaning up
-            let _ = self.clear();
+    /// Returns the row of the terminal at which the first line is drawn, if
+    /// lines are positioned absolutely rather than relative to the cursor.
+    fn origin(&self) -> Option<u16> {
+        match self.mode {
+            Mode::FullScreen => Some(0),
+            Mode::StatusBar { .. } => self.status_top,
+            _ => None,
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::panic::AssertUnwindSafe;
+    /// Returns the number of rows occupied by text before the first line, if
+    /// the start column is wider than the terminal and has been wrapped.
+    fn start_rows(&self) -> u16 {
+        match self.size {
+            Some((columns, _)) if self.mode == Mode::Inline => self.start_column / columns,
+            _ => 0,
+        }
+    }
 
-    use crate::element::{Cursor, IntoElement};
+    /// Returns the column of the terminal at which the given line starts.
+    fn line_start(&self, line: u16) -> u16 {
+        if line != 0 || self.mode != Mode::Inline {
+            return 0;
+        }
+        match self.size {
+            Some((columns, _)) => self.start_column % columns,
+            None => self.start_column,
+        }
+    }
 
-    use super::*;
+    /// Returns the maximum width of the given line on the terminal.
+    fn line_width(&self, line: u16) -> usize {
+        match self.size {
+            Some((columns, _)) => (columns - self.line_start(line)) as usize,
+            None => usize::MAX,
+        }
+    }
 
-    #[test]
-    fn empty() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
-        for _ in 0..3 {
-            r.writer.clear();
-            r.reset()?.finish()?;
-            assert_eq!(r.writer, b"\r\x1b[?25l");
+    /// Moves the terminal cursor to the given line, leaving the column as-is.
+    fn move_to_line(&mut self, line: u16) -> io::Result<()> {
+        let current_line = self.cursor.0;
+        if self.origin().is_some() {
+            if line != current_line {
+                return self.move_to(line, self.cursor.1.unwrap_or(0));
+            }
+            return Ok(());
+        }
+        if line < current_line {
+            write!(self.buffer, "{}", cursor::Up(current_line - line))?;
+        } else if line > current_line {
+            // Lines that are already on the screen can be reached by moving
+            // the cursor down, but new lines must be created with newlines.
+            let last_line = (self.screen.len() as u16)
+                .saturating_sub(1)
+                .max(current_line);
+            let down = line.min(last_line) - current_line;
+            if down != 0 {
+                write!(self.buffer, "{}", cursor::Down(down))?;
+            }
+            for _ in last_line..line {
+                self.buffer.write_all(b"\n")?;
+            }
         }
+        // Columns are relative to the start of the line, and the first line
+        // may start at a different column from the others.
+        let start = self.line_start(0);
+        if current_line == 0 && line != 0 {
+            self.cursor.1 = self.cursor.1.map(|column| column.saturating_add(start));
+        } else if current_line != 0 && line == 0 {
+            self.cursor.1 = self.cursor.1.and_then(|column| column.checked_sub(start));
+        }
+        self.cursor.0 = line;
         Ok(())
     }
 
-    #[test]
-    fn empty_line() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
-        for _ in 0..3 {
-            r.writer.clear();
-            r.reset()?.render(())?.finish()?;
-            assert_eq!(r.writer, b"\r\x1b[K\x1b[?25l");
+    /// Moves the terminal cursor to the given position.
+    fn move_to(&mut self, line: u16, column: u16) -> io::Result<()> {
+        if let Some(origin) = self.origin() {
+            if self.cursor != (line, Some(column)) {
+                // `Goto` is 1-based. The terminal clamps the position to the
+                // screen anyway, so saturating is harmless.
+                let row = origin.saturating_add(line).saturating_add(1);
+                let goto = cursor::Goto(column.saturating_add(1), row);
+                write!(self.buffer, "{goto}")?;
+                self.cursor = (line, Some(column));
+            }
+            return Ok(());
+        }
+        self.move_to_line(line)?;
+        if self.cursor.1 != Some(column) {
+            write!(self.buffer, "\r")?;
+            let terminal_column = self.line_start(line).saturating_add(column);
+            if terminal_column != 0 {
+                write!(self.buffer, "{}", cursor::Right(terminal_column))?;
+            }
+            self.cursor.1 = Some(column);
         }
         Ok(())
     }
 
-    #[test]
-    fn one_line() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
-        for _ in 0..3 {
-            r.writer.clear();
-            r.reset()?.render("trans rights".into_element())?.finish()?;
-            assert_eq!(r.writer, b"\rtrans rights\x1b[m\x1b[K\x1b[?25l");
+    /// Shows or hides the terminal cursor, if necessary.
+    fn set_cursor_visible(&mut self, visible: bool) -> io::Result<()> {
+        if self.cursor_visible != Some(visible) {
+            if visible {
+                write!(self.buffer, "{}", cursor::Show)?;
+            } else {
+                write!(self.buffer, "{}", cursor::Hide)?;
+            }
+            self.cursor_visible = Some(visible);
         }
         Ok(())
     }
 
-    #[test]
-    fn two_lines() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
-        r.reset()?
-            .render("trans rights".into_element())?
-            .render("enby rights".into_element())?
-            .finish()?;
-        assert_eq!(
-            r.writer,
-            b"\rtrans rights\x1b[m\x1b[K\n\renby rights\x1b[m\x1b[K\x1b[?25l",
-        );
-
-        for _ in 0..3 {
-            r.writer.clear();
-            r.reset()?
-                .render("trans rights".into_element())?
-                .render("enby rights".into_element())?
-                .finish()?;
-            assert_eq!(
-                r.writer,
-                b"\x1b[1A\rtrans rights\x1b[m\x1b[K\n\renby rights\x1b[m\x1b[K\x1b[?25l",
-            );
+    /// Changes the shape of the terminal cursor, if necessary.
+    fn set_cursor_shape(&mut self, shape: Option<CursorShape>) -> io::Result<()> {
+        if self.cursor_shape != shape {
+            write!(self.buffer, "{}", SetCursorShape(shape))?;
+            self.cursor_shape = shape;
         }
         Ok(())
     }
 
-    #[test]
-    fn drop() {
-        let mut out = vec![];
-        Renderer::new(&mut out);
-        assert_eq!(out, b"\r\x1b[J\x1b[?25h");
+    /// Starts rendering a new frame.
+    ///
+    /// This is an alternative to calling [`reset`](Self::reset),
+    /// [`render`](Self::render), and [`finish`](Self::finish) directly; the
+    /// returned [`Frame`] is finished automatically when it is dropped.
+    pub fn frame(&mut self) -> Result<Frame<'_, W>> {
+        Frame::new(self)
     }
 
-    #[test]
-    fn cursor_at_start_of_last_line() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
-        r.reset()?
-            .render("trans rights".into_element())?
-            .render((Cursor, "enby rights".into_element()))?
-            .finish()?;
-        assert_eq!(
-            r.writer,
-            b"\rtrans rights\x1b[m\x1b[K\n\renby rights\x1b[m\x1b[K\r\x1b[?25h",
-        );
-        Ok(())
+    /// Renders a frame using the provided closure.
+    ///
+    /// The frame is finished after the closure returns, even if it returns an
+    /// error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use line_ui::Renderer;
+    /// use line_ui::element::IntoElement;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut r = Renderer::new(vec![]);
+    /// r.draw(|frame| {
+    ///     frame.render("hello".into_element())?;
+    ///     frame.render("world".into_element())?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn draw<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Frame<'_, W>) -> Result<T>,
+    {
+        let mut frame = self.frame()?;
+        let result = f(&mut frame);
+        let finished = frame.finish();
+        let value = result?;
+        finished?;
+        Ok(value)
     }
 
-    #[test]
-    fn cursor_in_last_line() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
-        r.reset()?
-            .render("trans rights".into_element())?
-            .render(("enby ".into_element(), Cursor, "rights".into_element()))?
-            .finish()?;
-        assert_eq!(
-            r.writer,
-            b"\rtrans rights\x1b[m\x1b[K\n\renby \x1b[mrights\x1b[m\x1b[K\r\x1b[5C\x1b[?25h",
-        );
-        Ok(())
+    /// Returns an error if a frame is being rendered.
+    fn check_finished(&self) -> Result<()> {
+        if self.is_dirty {
+            Err(Error::Unfinished)
+        } else {
+            Ok(())
+        }
     }
 
-    #[test]
-    fn cursor_in_previous_line() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
-        r.reset()?
-            .render(("trans rights".into_element(), Cursor))?
-            .render("enby rights".into_element())?
-            .finish()?;
-        assert_eq!(
-            r.writer,
-            b"\rtrans rights\x1b[m\x1b[K\n\renby rights\x1b[m\x1b[K\x1b[1A\r\x1b[12C\x1b[?25h",
-        );
-        Ok(())
+    /// Resets the cursor position, allowing rendering to start over.
+    ///
+    /// Returns [`Error::Unfinished`] if the previous frame has not been
+    /// [finished](Self::finish).
+    pub fn reset(&mut self) -> Result<&mut Self> {
+        self.check_finished()?;
+        // The cursor is moved lazily, once a line needs to be rewritten.
+        self.reset_state();
+        let size = query_size(&mut self.size_source);
+        if size != self.size {
+            self.resize(size)?;
+        }
+        Ok(self)
     }
 
-    #[test]
-    fn leave_empty() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
-        r.reset()?.finish()?;
-        r.writer.clear();
-        r.leave()?;
-        assert_eq!(r.writer, b"");
+    /// Checks whether the size of the terminal has changed, and if so, clears
+    /// the rendered lines and redraws the most recent frame at the new size.
+    /// Returns whether the size has changed.
+    ///
+    /// The size is also checked at the start of each frame, so this only needs
+    /// to be called to respond to a resize between frames, e.g., upon
+    /// receiving `SIGWINCH`.
+    ///
+    /// Returns [`Error::Unfinished`] if a frame is being rendered.
+    pub fn check_size(&mut self) -> Result<bool> {
+        self.check_finished()?;
+        let size = query_size(&mut self.size_source);
+        if size == self.size {
+            return Ok(false);
+        }
+        self.resize(size)?;
+        self.redraw()?;
+        self.flush_buffer()?;
+        Ok(true)
+    }
+
+    /// Draws the most recent frame again, truncating its lines to the
+    /// current size.
+    fn redraw(&mut self) -> Result<()> {
+        if self.mode != Mode::Plain && !self.frame.is_empty() {
+            self.draw_frame()?;
+        }
         Ok(())
     }
 
-    #[test]
+    /// Clears the UI, like [`clear`](Self::clear), but keeps the most recent
+    /// frame so that it can be redrawn by [`resume`](Self::resume).
+    ///
+    /// This can be used to hand the terminal over to something else
+    /// temporarily, e.g., when the process is stopped with Ctrl-Z.
+    ///
+    /// Returns [`Error::Unfinished`] if a frame is being rendered.
+    pub fn suspend(&mut self) -> Result<()> {
+        self.flush()?;
+        let frame = std::mem::take(&mut self.frame);
+        let desired_cursor = self.desired_cursor;
+        let desired_cursor_shape = self.desired_cursor_shape;
+        self.clear()?;
+        self.frame = frame;
+        self.desired_cursor = desired_cursor;
+        self.desired_cursor_shape = desired_cursor_shape;
+        Ok(())
+    }
+
+    /// Redraws the most recent frame after a call to
+    /// [`suspend`](Self::suspend), at the current size of the terminal.
+    ///
+    /// Returns [`Error::Unfinished`] if a frame is being rendered.
+    pub fn resume(&mut self) -> Result<()> {
+        self.check_finished()?;
+        let size = query_size(&mut self.size_source);
+        if size != self.size {
+            self.resize(size)?;
+        }
+        // Redraw everything, in case the terminal was used in the meantime.
+        self.screen.clear();
+        self.cursor = (0, None);
+        self.cursor_visible = None;
+        self.redraw()?;
+        Ok(self.flush_buffer()?)
+    }
+
+    /// Hands the terminal over to the given function, e.g., to run an
+    /// interactive program such as `$EDITOR` or `less`, and returns its result.
+    ///
+    /// Beforehand, the UI is [suspended](Self::suspend) and the terminal's
+    /// original mode is restored, with the cursor shown. Afterwards, raw mode
+    /// is enabled again and the most recent frame is
+    /// [redrawn](Self::resume).
+    ///
+    /// Returns [`Error::Unfinished`] if a frame is being rendered.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::process::Command;
+    ///
+    /// use line_ui::Renderer;
+    /// use termion::raw::IntoRawMode;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut r = Renderer::new(std::io::stdout().into_raw_mode()?);
+    /// // ...
+    /// let status = r.run_external(|| Command::new("vi").arg("notes.txt").status())??;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run_external<T>(&mut self, f: impl FnOnce() -> T) -> Result<T>
+    where
+        W: RawMode,
+    {
+        self.suspend()?;
+        self.writer.suspend_raw_mode()?;
+        let value = f();
+        self.writer.activate_raw_mode()?;
+        self.resume()?;
+        Ok(value)
+    }
+
+    /// Changes the size of the terminal, clearing the rendered lines.
+    ///
+    /// When a terminal becomes narrower, it may rewrap lines that no longer
+    /// fit, so the cursor is moved up past any rows that the rendered lines
+    /// could now occupy.
+    fn resize(&mut self, size: Option<(u16, u16)>) -> io::Result<()> {
+        self.size = size;
+        if self.screen.is_empty() && self.status_top.is_none() {
+            return Ok(());
+        }
+        match self.mode {
+            Mode::Plain => {}
+            Mode::FullScreen => {
+                write!(self.buffer, "{}{}", cursor::Goto(1, 1), clear::All)?;
+            }
+            Mode::StatusBar { .. } => {
+                // The scroll region is set up again when the next frame is
+                // drawn, which clears the status region at its new position.
+                if let Some(top) = self.status_top.take() {
+                    let top = match size {
+                        Some((_, rows)) => top.min(rows.saturating_sub(1)),
+                        None => top,
+                    };
+                    write!(self.buffer, "{SAVE_CURSOR}{}", cursor::Goto(1, top + 1))?;
+                    write!(self.buffer, "{}{RESTORE_CURSOR}", clear::AfterCursor)?;
+                }
+            }
+            Mode::Inline => {
+                let (line, column) = self.cursor;
+                let line = line as usize;
+                let rows = |width: usize| match size {
+                    Some((columns, _)) => width.saturating_sub(1) / columns as usize + 1,
+                    None => 1,
+                };
+                // The text before the first line is rewrapped along with it.
+                let start = |index: usize| {
+                    if index == 0 {
+                        self.start_column as usize
+                    } else {
+                        0
+                    }
+                };
+                let current_width = self.screen.get(line).map_or(0, |line| line.width);
+                let column = column.map_or(current_width, |column| column as usize + 1);
+                let up = self
+                    .screen
+                    .iter()
+                    .enumerate()
+                    .take(line)
+                    .map(|(index, line)| rows(start(index) + line.width))
+                    .sum::<usize>()
+                    + rows(start(line) + column)
+                    - 1;
+                let up = up.saturating_sub(self.start_rows() as usize);
+                if up != 0 {
+                    let up = u16::try_from(up).unwrap_or(u16::MAX);
+                    write!(self.buffer, "{}", cursor::Up(up))?;
+                }
+                write!(self.buffer, "\r")?;
+                let start = self.line_start(0);
+                if start != 0 {
+                    write!(self.buffer, "{}", cursor::Right(start))?;
+                }
+                write!(self.buffer, "{}", clear::AfterCursor)?;
+            }
+        }
+        self.screen.clear();
+        self.cursor = (0, Some(0));
+        Ok(())
+    }
+
+    /// Clears the UI, resetting the terminal back to its initial state.
+    ///
+    /// Note that this method is automatically called when the `Renderer` is
+    /// [dropped](Drop).
+    pub fn clear(&mut self) -> Result<()> {
+        self.flush()?;
+        if self.mode == Mode::Plain {
+            self.reset_state();
+            return Ok(self.flush_buffer()?);
+        }
+        self.set_cursor_shape(None)?;
+        if self.mode == Mode::FullScreen {
+            self.leave_alternate_screen(Vec::new())?;
+            self.reset_state();
+            return Ok(self.flush_buffer()?);
+        }
+        if let Mode::StatusBar { .. } = self.mode {
+            self.remove_status_region()?;
+            self.reset_state();
+            return Ok(self.flush_buffer()?);
+        }
+        self.move_to(0, 0)?;
+        write!(self.buffer, "{}", clear::AfterCursor)?;
+        self.set_cursor_visible(true)?;
+        self.screen.clear();
+        self.reset_state();
+        Ok(self.flush_buffer()?)
+    }
+
+    /// Renders a line.
+    ///
+    /// The line is drawn when the frame is [finished](Self::finish). If the
+    /// line is identical to the line at the same position in the previous
+    /// frame, nothing is written. If the line is wider than the terminal, it
+    /// is truncated, except in [`Mode::Plain`].
+    ///
+    /// The line is rendered with [`Element::render_in`], given the width of
+    /// the terminal, so that it can adapt to a narrow terminal.
+    ///
+    /// Returns [`Error::TooManyLines`] if the frame already has
+    /// [`u16::MAX`] lines.
+    pub fn render<'s, E: Element<'s>>(&mut self, line: E) -> Result<&mut Self> {
+        self.is_dirty = true;
+        let index = u16::try_from(self.frame.len())
+            .ok()
+            .filter(|&index| index != u16::MAX)
+            .ok_or(Error::TooManyLines)?;
+        // Plain text is a log rather than a display, so it is never truncated.
+        let max_width = match self.size {
+            Some((columns, _)) if self.mode != Mode::Plain => columns as usize,
+            _ => usize::MAX,
+        };
+        // The first line may start after some text that is already displayed.
+        let available_width = max_width - usize::from(self.line_start(index));
+        // Collect each chunk. The line is kept in full, and only truncated
+        // when it is drawn, so that it can be redrawn if the terminal becomes
+        // wider.
+        let mut content = Line::default();
+        for chunk in line.render_in(available_width) {
+            if chunk.cursor {
+                debug_assert_eq!(chunk.value, "");
+                debug_assert_eq!(chunk.width, 0);
+                // The cursor is kept within the terminal when it is placed.
+                let column = u16::try_from(content.width).unwrap_or(u16::MAX);
+                self.desired_cursor = Some((index, column));
+                self.desired_cursor_shape = chunk.cursor_shape;
+            } else {
+                content.push(chunk);
+            }
+        }
+        self.frame.push(content);
+        Ok(self)
+    }
+
+    /// Renders each line of a [`Block`].
+    ///
+    /// See [`render`](Self::render) for details.
+    pub fn render_block<'s, B: Block<'s>>(&mut self, block: B) -> Result<&mut Self> {
+        for line in block.lines() {
+            self.render(line)?;
+        }
+        Ok(self)
+    }
+
+    /// Writes a line to the terminal, unless it is already displayed.
+    fn update_line(&mut self, index: u16, content: &Line) -> io::Result<()> {
+        let max_width = self.line_width(index);
+        if content.width > max_width {
+            return self.update_line(index, &content.truncated(max_width));
+        }
+        if self.screen.get(index as usize) == Some(content) {
+            return Ok(());
+        }
+        self.move_to(index, 0)?;
+        let chunks = content.chunks.iter();
+        write_styled(
+            &mut self.buffer,
+            chunks.map(|chunk| (&*chunk.value, chunk.style)),
+        )?;
+        if content.width < max_width {
+            write!(self.buffer, "{}", clear::UntilNewline)?;
+            // A column that cannot be represented is treated as unknown.
+            self.cursor.1 = u16::try_from(content.width).ok();
+        } else {
+            // The line fills the terminal, so there is nothing to clear. The
+            // terminal may or may not have wrapped the cursor, so its column
+            // is unknown.
+            self.cursor.1 = None;
+        }
+        match self.screen.get_mut(index as usize) {
+            Some(old) => old.clone_from(content),
+            None => self.screen.push(content.clone()),
+        }
+        Ok(())
+    }
+
+    /// Returns the number of rows in the status region, which is zero if the
+    /// renderer is not in [`Mode::StatusBar`] or the size is unknown.
+    fn status_rows(&self) -> u16 {
+        match (self.mode, self.size) {
+            (Mode::StatusBar { rows }, Some((_, terminal_rows))) => {
+                rows.min(terminal_rows.saturating_sub(1))
+            }
+            _ => 0,
+        }
+    }
+
+    /// Sets up the scroll region above the status region, if necessary.
+    fn set_up_status_region(&mut self) -> io::Result<()> {
+        let Some((_, rows)) = self.size else {
+            return Ok(());
+        };
+        let status_rows = self.status_rows();
+        let top = rows - status_rows;
+        if self.status_top == Some(top) {
+            return Ok(());
+        }
+        // Make room for the status region by scrolling the existing output
+        // up, while keeping the cursor on the same line of output.
+        for _ in 0..status_rows {
+            self.buffer.write_all(b"\n")?;
+        }
+        write!(self.buffer, "{}{SAVE_CURSOR}", cursor::Up(status_rows))?;
+        // Setting the scroll region moves the cursor, so it is restored
+        // afterwards.
+        write!(self.buffer, "\x1b[1;{top}r")?;
+        write!(
+            self.buffer,
+            "{}{}",
+            cursor::Goto(1, top + 1),
+            clear::AfterCursor
+        )?;
+        write!(self.buffer, "{RESTORE_CURSOR}")?;
+        self.status_top = Some(top);
+        self.screen.clear();
+        Ok(())
+    }
+
+    /// Clears the status region and removes the scroll region, if it has been
+    /// set up.
+    fn remove_status_region(&mut self) -> io::Result<()> {
+        if let Some(top) = self.status_top.take() {
+            write!(self.buffer, "{SAVE_CURSOR}{}", cursor::Goto(1, top + 1))?;
+            write!(self.buffer, "{}{RESET_SCROLL_REGION}", clear::AfterCursor)?;
+            write!(self.buffer, "{RESTORE_CURSOR}")?;
+        }
+        self.screen.clear();
+        self.cursor = (0, None);
+        Ok(())
+    }
+
+    /// Draws the current frame.
+    fn draw_frame(&mut self) -> io::Result<()> {
+        if self.mode == Mode::Plain {
+            self.pending = false;
+            return Ok(());
+        }
+        if let Mode::StatusBar { .. } = self.mode {
+            if self.status_rows() == 0 {
+                self.pending = false;
+                return Ok(());
+            }
+            self.set_up_status_region()?;
+            // Lines are drawn with absolute positions, and the cursor is then
+            // returned to the scroll region.
+            write!(self.buffer, "{SAVE_CURSOR}")?;
+            self.cursor = (0, None);
+        }
+        if self.mode == Mode::FullScreen && !self.alternate_screen {
+            write!(self.buffer, "{ToAlternateScreen}{}", clear::All)?;
+            write!(self.buffer, "{}", cursor::Goto(1, 1))?;
+            self.alternate_screen = true;
+            self.screen.clear();
+            self.cursor = (0, Some(0));
+        }
+        let frame = std::mem::take(&mut self.frame);
+        let visible = self.update_viewport(frame.len());
+        let result = self.draw_lines(&frame[visible]);
+        self.frame = frame;
+        result?;
+        if self.status_top.is_some() {
+            write!(self.buffer, "{RESTORE_CURSOR}")?;
+            self.cursor = (0, None);
+        }
+        self.pending = false;
+        self.last_draw = Some(Instant::now());
+        self.flush_buffer()
+    }
+
+    /// Adjusts the scroll offset for a frame with the given number of lines,
+    /// and returns the range of lines that are visible.
+    fn update_viewport(&mut self, lines: usize) -> Range<usize> {
+        let height = match (self.mode, self.size) {
+            (Mode::StatusBar { .. }, _) => self.status_rows() as usize,
+            (_, Some((_, rows))) => rows as usize,
+            (_, None) => usize::MAX,
+        };
+        let mut offset = self.scroll_offset.min(lines.saturating_sub(height));
+        if let Some((line, _)) = self.desired_cursor
+            && self.follow_cursor
+        {
+            let line = line as usize;
+            if line < offset {
+                offset = line;
+            } else if line - offset >= height {
+                offset = line + 1 - height;
+            }
+        }
+        self.scroll_offset = offset;
+        offset..lines.min(offset.saturating_add(height))
+    }
+
+    fn draw_lines(&mut self, frame: &[Line]) -> io::Result<()> {
+        for (index, line) in frame.iter().enumerate() {
+            self.update_line(index as u16, line)?;
+        }
+        // Clear any leftover lines from the previous frame.
+        if self.screen.len() > frame.len() {
+            self.move_to(frame.len() as u16, 0)?;
+            write!(self.buffer, "{}", clear::AfterCursor)?;
+            self.screen.truncate(frame.len());
+        }
+        if self.status_top.is_some() {
+            return Ok(());
+        }
+        self.place_cursor()
+    }
+
+    /// Moves the terminal cursor to its desired position, or hides it.
+    fn place_cursor(&mut self) -> io::Result<()> {
+        // The desired cursor may be on a line that is scrolled out of view.
+        let offset = self.scroll_offset as u16;
+        let desired_cursor = self.desired_cursor.and_then(|(line, column)| {
+            let line = line.checked_sub(offset)?;
+            ((line as usize) < self.screen.len()).then_some((line, column))
+        });
+        if let Some((line, column)) = desired_cursor {
+            // The cursor cannot be placed past the last column.
+            let max_column = self.line_width(line).saturating_sub(1);
+            let column = column.min(u16::try_from(max_column).unwrap_or(u16::MAX));
+            self.move_to(line, column)?;
+            self.set_cursor_shape(self.desired_cursor_shape)?;
+            self.set_cursor_visible(true)
+        } else {
+            // Keep the cursor within the rendered lines.
+            let lines = self.screen.len() as u16;
+            if self.cursor.0 >= lines && lines != 0 {
+                self.move_to_line(lines - 1)?;
+            }
+            self.set_cursor_visible(false)
+        }
+    }
+
+    /// Finishes rendering. This should be called immediately after the
+    /// [`render`](Self::render) calls are complete.
+    ///
+    /// If fewer lines were rendered than in the previous frame, the leftover
+    /// lines are cleared. If the [minimum interval](Self::with_min_interval)
+    /// has not elapsed since the previous frame was drawn, the frame is left
+    /// pending instead.
+    pub fn finish(&mut self) -> Result<()> {
+        self.is_dirty = false;
+        self.pending = true;
+        if self.pending_timeout() == Some(Duration::ZERO) {
+            self.draw_frame()?;
+        }
+        Ok(())
+    }
+
+    /// Draws the pending frame, if there is one, regardless of the
+    /// [minimum interval](Self::with_min_interval).
+    pub fn flush(&mut self) -> Result<()> {
+        self.check_finished()?;
+        if self.pending {
+            self.draw_frame()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the time remaining until the pending frame can be drawn, or
+    /// `None` if there is no pending frame.
+    ///
+    /// This can be used as a timeout when waiting for input, after which
+    /// [`flush`](Self::flush) should be called.
+    pub fn pending_timeout(&self) -> Option<Duration> {
+        if !self.pending {
+            return None;
+        }
+        Some(match self.last_draw {
+            Some(last_draw) => self.min_interval.saturating_sub(last_draw.elapsed()),
+            None => Duration::ZERO,
+        })
+    }
+
+    /// Prints a line above the rendered lines.
+    ///
+    /// Unlike rendered lines, printed lines are permanent; they scroll into
+    /// the terminal's history, and are not truncated to the terminal width.
+    /// The most recent frame is redrawn below the printed line. This should be
+    /// called after [`finish`](Self::finish).
+    ///
+    /// The line starts at the beginning of a row, or after the
+    /// [start column](Self::with_start_column) on the row of the first line.
+    /// In [`Mode::StatusBar`], the line is written at the cursor in the scroll
+    /// region, which is assumed to be at the beginning of a row unless a
+    /// start column has been set; in that case, a new row is started first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use line_ui::Renderer;
+    /// use line_ui::element::IntoElement;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut r = Renderer::new(vec![]);
+    /// r.reset()?.render("Compiling...".into_element())?.finish()?;
+    /// r.print("Compiled foo")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn print<'s, E: IntoElement<'s>>(&mut self, line: E) -> Result<()> {
+        self.print_lines([line])
+    }
+
+    /// Prints multiple lines above the rendered lines.
+    ///
+    /// See [`print`](Self::print) for details.
+    pub fn print_lines<'s, I>(&mut self, lines: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: IntoElement<'s>,
+    {
+        self.check_finished()?;
+        if self.mode == Mode::Plain {
+            for line in lines {
+                self.write_plain(line.into_element())?;
+            }
+            return Ok(self.flush_buffer()?);
+        }
+        if self.mode == Mode::FullScreen {
+            let lines = lines.into_iter().map(|line| Line::new(line.into_element()));
+            self.deferred.extend(lines);
+            return self.flush();
+        }
+        if let Mode::StatusBar { .. } = self.mode {
+            // The lines scroll within the scroll region, leaving the status
+            // region untouched.
+            self.start_new_row()?;
+            for line in lines {
+                self.write_permanent(line.into_element())?;
+            }
+            self.flush()?;
+            return Ok(self.flush_buffer()?);
+        }
+        self.move_to(0, 0)?;
+        write!(self.buffer, "{}", clear::AfterCursor)?;
+        for line in lines {
+            self.write_permanent(line.into_element())?;
+        }
+
+        // Redraw the most recent frame below the printed lines, at the start
+        // of a new row.
+        self.start_column = 0;
+        self.cursor = (0, Some(0));
+        self.screen.clear();
+        Ok(self.draw_frame()?)
+    }
+
+    /// Takes the lines of the most recent frame, truncated to the width of
+    /// the terminal as they were displayed.
+    fn take_truncated_frame(&mut self) -> Vec<Line> {
+        let frame = std::mem::take(&mut self.frame);
+        let width = match self.size {
+            Some((columns, _)) => columns as usize,
+            None => return frame,
+        };
+        frame.iter().map(|line| line.truncated(width)).collect()
+    }
+
+    /// Moves the cursor to the start of the next row, if it follows text
+    /// before the start column. This is used in [`Mode::StatusBar`], in which
+    /// the cursor stays where the output before the renderer left it.
+    fn start_new_row(&mut self) -> io::Result<()> {
+        if self.start_column != 0 {
+            write!(self.buffer, "\r\n")?;
+            self.start_column = 0;
+        }
+        Ok(())
+    }
+
+    /// Writes a line that scrolls into the terminal's history, leaving the
+    /// cursor at the start of the next line.
+    fn write_permanent<'s, E: Element<'s>>(&mut self, line: E) -> io::Result<()> {
+        let chunks = line.render().map(|chunk| (chunk.value, chunk.style));
+        write_styled(&mut self.buffer, chunks)?;
+        write!(self.buffer, "\n\r")
+    }
+
+    /// Returns to the main screen, if the alternate screen has been entered.
+    /// Any lines that were printed in the meantime are written, followed by
+    /// the given lines.
+    fn leave_alternate_screen(&mut self, mut lines: Vec<Line>) -> io::Result<()> {
+        if self.alternate_screen {
+            write!(self.buffer, "{ToMainScreen}")?;
+            self.alternate_screen = false;
+        }
+        // The main screen's cursor is restored to where it was before the
+        // alternate screen was entered.
+        self.screen.clear();
+        self.cursor = (0, None);
+        self.set_cursor_visible(true)?;
+        lines.splice(0..0, std::mem::take(&mut self.deferred));
+        if !lines.is_empty() {
+            write!(self.buffer, "\r")?;
+            for line in &lines {
+                self.write_permanent(line)?;
+            }
+            self.cursor = (0, Some(0));
+        }
+        Ok(())
+    }
+
+    /// Writes a line as plain text, without any styles.
+    fn write_plain<'s, E: Element<'s>>(&mut self, line: E) -> io::Result<()> {
+        for chunk in line.render() {
+            self.buffer.write_all(chunk.value.as_bytes())?;
+        }
+        self.buffer.write_all(b"\n")
+    }
+
+    /// Leaves the currently-rendered text, making it impossible to clear.
+    ///
+    /// This method may be used if you want to dispose of this `Renderer`
+    /// without clearing the currently-rendered text. This should be called
+    /// after [`finish`](Self::finish).
+    pub fn leave(&mut self) -> Result<()> {
+        self.flush()?;
+        if self.mode == Mode::Plain {
+            for line in std::mem::take(&mut self.frame) {
+                self.write_plain(&line)?;
+            }
+            self.reset_state();
+            return Ok(self.flush_buffer()?);
+        }
+        if self.mode == Mode::FullScreen {
+            let frame = self.take_truncated_frame();
+            self.leave_alternate_screen(frame)?;
+            self.reset_state();
+            return Ok(self.flush_buffer()?);
+        }
+        if let Mode::StatusBar { .. } = self.mode {
+            // The status region is replaced by permanent lines.
+            let frame = self.take_truncated_frame();
+            self.remove_status_region()?;
+            self.start_new_row()?;
+            for line in &frame {
+                self.write_permanent(line)?;
+            }
+            self.reset_state();
+            return Ok(self.flush_buffer()?);
+        }
+        if self.screen.is_empty() {
+            return Ok(());
+        }
+        self.move_to_line(self.screen.len() as u16 - 1)?;
+        write!(self.buffer, "\n\r")?;
+        self.start_column = 0;
+        self.screen.clear();
+        self.cursor = (0, Some(0));
+        self.reset_state();
+        Ok(self.flush_buffer()?)
+    }
+}
+
+/// Writes a line of styled text.
+///
+/// Only the attributes that change between consecutive chunks are written.
+/// The style is reset at the end of the line, since some terminals fill erased
+/// cells and new lines with the current background color.
+fn write_styled<'a, I>(writer: &mut impl Write, chunks: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = (&'a str, Style)>,
+{
+    let mut current = Style::EMPTY;
+    for (value, style) in chunks {
+        if !value.is_empty() {
+            write!(writer, "{}{value}", current.transition(style))?;
+            current = style;
+        }
+    }
+    write!(writer, "{}", current.transition(Style::EMPTY))
+}
+
+impl<W: Write> Drop for Renderer<W> {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            // If dropping due to panic, don't bother cleaning up; the panic
+            // hook may have done so already. An unfinished frame is
+            // discarded.
+            self.is_dirty = false;
+            let _ = self.clear();
+        }
+        if let Some(id) = self.restore_id {
+            restore::remove(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::AssertUnwindSafe;
+    use std::sync::{Arc, Mutex};
+
+    use crate::element::{Abbreviated, Cursor, CursorShape, IntoElement};
+    use crate::testing::Screen;
+
+    use super::*;
+
+    #[test]
+    fn empty() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?.finish()?;
+        assert_eq!(r.writer, b"\x1b[?25l");
+        for _ in 0..3 {
+            r.writer.clear();
+            r.reset()?.finish()?;
+            assert_eq!(r.writer, b"");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn empty_line() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?.render(())?.finish()?;
+        assert_eq!(r.writer, b"\r\x1b[K\x1b[?25l");
+        for _ in 0..3 {
+            r.writer.clear();
+            r.reset()?.render(())?.finish()?;
+            assert_eq!(r.writer, b"");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn one_line() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        assert_eq!(r.writer, b"\rtrans rights\x1b[K\x1b[?25l");
+        for _ in 0..3 {
+            r.writer.clear();
+            r.reset()?.render("trans rights".into_element())?.finish()?;
+            assert_eq!(r.writer, b"");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn two_lines() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        assert_eq!(
+            r.writer,
+            b"\rtrans rights\x1b[K\n\renby rights\x1b[K\x1b[?25l",
+        );
+
+        for _ in 0..3 {
+            r.writer.clear();
+            r.reset()?
+                .render("trans rights".into_element())?
+                .render("enby rights".into_element())?
+                .finish()?;
+            assert_eq!(r.writer, b"");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn changed_first_line() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        r.writer.clear();
+        r.reset()?
+            .render("human rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        assert_eq!(r.writer, b"\x1b[1A\rhuman rights\x1b[K");
+        Ok(())
+    }
+
+    #[test]
+    fn changed_last_line() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        r.writer.clear();
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("human rights".into_element())?
+            .finish()?;
+        assert_eq!(r.writer, b"\rhuman rights\x1b[K");
+        Ok(())
+    }
+
+    #[test]
+    fn changed_line_below_cursor() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .render("enby rights".into_element())?
+            .render("human rights".into_element())?
+            .finish()?;
+        r.writer.clear();
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .render("enby rights".into_element())?
+            .render("all rights".into_element())?
+            .finish()?;
+        assert_eq!(r.writer, b"\x1b[2B\rall rights\x1b[K\x1b[2A\r\x1b[12C",);
+        Ok(())
+    }
+
+    #[test]
+    fn changed_style() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        r.writer.clear();
+        r.reset()?
+            .render("trans rights".styled(Style::BOLD))?
+            .finish()?;
+        assert_eq!(r.writer, b"\r\x1b[1mtrans rights\x1b[m\x1b[K");
+        Ok(())
+    }
+
+    #[test]
+    fn style_transitions() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render((
+                "trans ".styled(Style::BOLD),
+                "rights".styled(Style::BOLD),
+                " and ".styled(Style::BOLD + Style::ITALIC),
+                "enby rights".styled(Style::ITALIC),
+            ))?
+            .finish()?;
+        assert_eq!(
+            r.writer,
+            b"\r\x1b[1mtrans rights\x1b[3m and \x1b[22menby rights\x1b[m\x1b[K\x1b[?25l",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn added_line() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .finish()?;
+        r.writer.clear();
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .render("enby rights".into_element())?
+            .finish()?;
+        assert_eq!(r.writer, b"\n\renby rights\x1b[K\x1b[1A\r\x1b[12C");
+        Ok(())
+    }
+
+    #[test]
+    fn drop() {
+        let mut out = vec![];
+        Renderer::for_test(&mut out);
+        assert_eq!(out, b"\r\x1b[J\x1b[?25h");
+    }
+
+    #[test]
+    fn cursor_at_start_of_last_line() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render((Cursor, "enby rights".into_element()))?
+            .finish()?;
+        assert_eq!(
+            r.writer,
+            b"\rtrans rights\x1b[K\n\renby rights\x1b[K\r\x1b[?25h",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cursor_in_last_line() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render(("enby ".into_element(), Cursor, "rights".into_element()))?
+            .finish()?;
+        assert_eq!(
+            r.writer,
+            b"\rtrans rights\x1b[K\n\renby rights\x1b[K\r\x1b[5C\x1b[?25h",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cursor_in_previous_line() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .render("enby rights".into_element())?
+            .finish()?;
+        assert_eq!(
+            r.writer,
+            b"\rtrans rights\x1b[K\n\renby rights\x1b[K\x1b[1A\r\x1b[12C\x1b[?25h",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn removed_line() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .render("human rights".into_element())?
+            .finish()?;
+        r.writer.clear();
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        assert_eq!(r.writer, b"\x1b[1A\r\x1b[J\x1b[1A");
+
+        r.writer.clear();
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        assert_eq!(r.writer, b"\nenby rights\x1b[K");
+        Ok(())
+    }
+
+    #[test]
+    fn removed_all_lines() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        r.writer.clear();
+        r.reset()?.finish()?;
+        assert_eq!(r.writer, b"\x1b[1A\r\x1b[J");
+        Ok(())
+    }
+
+    #[test]
+    fn removed_line_with_cursor() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .render("human rights".into_element())?
+            .finish()?;
+        r.writer.clear();
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .render("enby rights".into_element())?
+            .finish()?;
+        assert_eq!(r.writer, b"\r\x1b[J\x1b[2A\r\x1b[12C\x1b[?25h");
+        Ok(())
+    }
+
+    #[test]
+    fn leave_after_removed_line() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        r.writer.clear();
+        r.leave()?;
+        assert_eq!(r.writer, b"\n\r");
+        Ok(())
+    }
+
+    #[test]
+    fn print() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        r.writer.clear();
+        r.print("hello".styled(Style::BOLD))?;
+        assert_eq!(
+            r.writer,
+            b"\x1b[1A\r\x1b[J\x1b[1mhello\x1b[m\n\rtrans rights\x1b[K\n\renby rights\x1b[K",
+        );
+
+        // The redrawn frame is used for diffing.
+        r.writer.clear();
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        assert_eq!(r.writer, b"");
+        Ok(())
+    }
+
+    #[test]
+    fn print_lines_with_cursor() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .finish()?;
+        r.writer.clear();
+        r.print_lines(["hello", "world"])?;
+        assert_eq!(r.writer, b"\r\x1b[Jhello\n\rworld\n\rtrans rights\x1b[K",);
+        Ok(())
+    }
+
+    #[test]
+    fn print_without_frame() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.print("hello")?;
+        assert_eq!(r.writer, b"\r\x1b[Jhello\n\r\x1b[?25l");
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_line() -> io::Result<()> {
+        let mut r = Renderer::new(vec![]).with_size_source(|| Some((8, 24)));
+        r.reset()?
+            .render(("trans ".into_element(), "rights".styled(Style::BOLD)))?
+            .render("enby rights".into_element())?
+            .finish()?;
+        assert_eq!(r.writer, b"\rtrans \x1b[1mri\x1b[m\n\renby rig\x1b[?25l",);
+        Ok(())
+    }
+
+    #[test]
+    fn zero_size() -> io::Result<()> {
+        let mut r = Renderer::new(vec![]).with_size_source(|| Some((0, 0)));
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        assert_eq!(r.writer, b"\rtrans rights\x1b[K\x1b[?25l");
+        Ok(())
+    }
+
+    #[test]
+    fn cursor_after_truncated_line() -> io::Result<()> {
+        let mut r = Renderer::new(vec![]).with_size_source(|| Some((8, 24)));
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .finish()?;
+        assert_eq!(r.writer, b"\rtrans ri\r\x1b[7C\x1b[?25h");
+        Ok(())
+    }
+
+    #[test]
+    fn size_queried_each_frame() -> io::Result<()> {
+        let size = Arc::new(Mutex::new((20, 24)));
+        let mut r = Renderer::new(vec![]).with_size_source({
+            let size = size.clone();
+            move || Some(*size.lock().unwrap())
+        });
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        r.writer.clear();
+        *size.lock().unwrap() = (5, 24);
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        // The terminal may have rewrapped the line onto three rows.
+        assert_eq!(r.writer, b"\x1b[2A\r\x1b[Jtrans");
+        Ok(())
+    }
+
+    #[test]
+    fn check_size() -> io::Result<()> {
+        let size = Arc::new(Mutex::new((20, 24)));
+        let mut r = Renderer::new(vec![]).with_size_source({
+            let size = size.clone();
+            move || Some(*size.lock().unwrap())
+        });
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .render("enby rights".into_element())?
+            .finish()?;
+        r.writer.clear();
+        assert!(!r.check_size()?);
+        assert_eq!(r.writer, b"");
+
+        *size.lock().unwrap() = (8, 24);
+        assert!(r.check_size()?);
+        assert_eq!(
+            r.writer,
+            b"\x1b[1A\r\x1b[Jtrans ri\n\renby rig\x1b[1A\r\x1b[7C",
+        );
+
+        // The redrawn frame is used for diffing.
+        r.writer.clear();
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .render("enby rights".into_element())?
+            .finish()?;
+        assert_eq!(r.writer, b"");
+        Ok(())
+    }
+
+    #[test]
+    fn check_size_wider() -> Result<()> {
+        // The screen is already wide, but the renderer is told that it is
+        // narrow at first.
+        let size = Arc::new(Mutex::new((6, 4)));
+        let mut r = Screen::new(12, 4).into_renderer().with_size_source({
+            let size = size.clone();
+            move || Some(*size.lock().unwrap())
+        });
+        r.reset()?
+            .render(("hello world".into_element(), Cursor))?
+            .finish()?;
+        assert_eq!(r.writer().line(0), "hello");
+        assert_eq!(r.writer().cursor(), (5, 0));
+
+        *size.lock().unwrap() = (12, 4);
+        assert!(r.check_size()?);
+        assert_eq!(r.writer().line(0), "hello world");
+        assert_eq!(r.writer().cursor(), (11, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn check_size_full_screen() -> io::Result<()> {
+        let size = Arc::new(Mutex::new((20, 24)));
+        let mut r = Renderer::new(vec![])
+            .with_mode(Mode::FullScreen)
+            .with_size_source({
+                let size = size.clone();
+                move || Some(*size.lock().unwrap())
+            });
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        r.writer.clear();
+        *size.lock().unwrap() = (5, 24);
+        assert!(r.check_size()?);
+        assert_eq!(r.writer, b"\x1b[1;1H\x1b[2Jtrans");
+        Ok(())
+    }
+
+    #[test]
+    fn min_interval() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]).with_min_interval(Duration::from_secs(3600));
+        assert_eq!(r.pending_timeout(), None);
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        assert_eq!(r.writer, b"\rtrans rights\x1b[K\x1b[?25l");
+        assert_eq!(r.pending_timeout(), None);
+
+        r.writer.clear();
+        r.reset()?.render("enby rights".into_element())?.finish()?;
+        r.reset()?.render("human rights".into_element())?.finish()?;
+        assert_eq!(r.writer, b"");
+        assert!(r.pending_timeout().unwrap() > Duration::from_secs(3000));
+
+        r.flush()?;
+        assert_eq!(r.writer, b"\rhuman rights\x1b[K");
+        assert_eq!(r.pending_timeout(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn min_interval_elapsed() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]).with_min_interval(Duration::from_millis(1));
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        std::thread::sleep(Duration::from_millis(5));
+        r.writer.clear();
+        r.reset()?.render("enby rights".into_element())?.finish()?;
+        assert_eq!(r.writer, b"\renby rights\x1b[K");
+        Ok(())
+    }
+
+    #[test]
+    fn leave_pending() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]).with_min_interval(Duration::from_secs(3600));
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        r.reset()?.render("enby rights".into_element())?.finish()?;
+        r.writer.clear();
+        r.leave()?;
+        assert_eq!(r.writer, b"\renby rights\x1b[K\n\r");
+        Ok(())
+    }
+
+    #[test]
+    fn drop_pending() {
+        let mut output = vec![];
+        let mut r = Renderer::for_test(&mut output).with_min_interval(Duration::from_secs(3600));
+        r.reset().unwrap().finish().unwrap();
+        r.reset()
+            .unwrap()
+            .render("trans rights".into_element())
+            .unwrap()
+            .finish()
+            .unwrap();
+        std::mem::drop(r);
+        assert_eq!(output, b"\x1b[?25l\rtrans rights\x1b[K\r\x1b[J\x1b[?25h",);
+    }
+
+    #[test]
+    fn full_screen() -> io::Result<()> {
+        let mut r = Renderer::new(vec![])
+            .with_mode(Mode::FullScreen)
+            .with_size_source(|| Some((20, 2)));
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render(("enby ".into_element(), Cursor, "rights".into_element()))?
+            .render("human rights".into_element())?
+            .finish()?;
+        assert_eq!(
+            r.writer,
+            b"\x1b[?1049h\x1b[2J\x1b[1;1Htrans rights\x1b[K\x1b[2;1Henby rights\x1b[K\x1b[2;6H\x1b[?25h",
+        );
+
+        r.writer.clear();
+        r.reset()?.render("all rights".into_element())?.finish()?;
+        assert_eq!(
+            r.writer,
+            b"\x1b[1;1Hall rights\x1b[K\x1b[2;1H\x1b[J\x1b[1;1H\x1b[?25l",
+        );
+
+        r.writer.clear();
+        r.print("hello")?;
+        assert_eq!(r.writer, b"");
+        r.clear()?;
+        assert_eq!(r.writer, b"\x1b[?1049l\x1b[?25h\rhello\n\r");
+        Ok(())
+    }
+
+    #[test]
+    fn full_screen_leave() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]).with_mode(Mode::FullScreen);
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        r.print("hello")?;
+        r.writer.clear();
+        r.leave()?;
+        assert_eq!(r.writer, b"\x1b[?1049l\x1b[?25h\rhello\n\rtrans rights\n\r",);
+
+        r.writer.clear();
+        r.clear()?;
+        assert_eq!(r.writer, b"");
+        Ok(())
+    }
+
+    fn render_numbered(r: &mut Renderer<Screen>, cursor: Option<usize>) -> Result<()> {
+        r.draw(|frame| {
+            for i in 0..5 {
+                let line = format!("line {i}");
+                frame.render((
+                    line.as_str().into_element(),
+                    (cursor == Some(i)).then_some(Cursor),
+                ))?;
+            }
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn viewport() -> Result<()> {
+        let mut r = Screen::new(20, 3).into_renderer();
+        render_numbered(&mut r, None)?;
+        assert_eq!(r.writer().lines(), ["line 0", "line 1", "line 2"]);
+        assert_eq!(r.scroll_offset(), 0);
+
+        render_numbered(&mut r, Some(4))?;
+        assert_eq!(r.writer().lines(), ["line 2", "line 3", "line 4"]);
+        assert_eq!(r.writer().cursor(), (6, 2));
+        assert_eq!(r.scroll_offset(), 2);
+
+        render_numbered(&mut r, Some(3))?;
+        assert_eq!(r.writer().lines(), ["line 2", "line 3", "line 4"]);
+        assert_eq!(r.writer().cursor(), (6, 1));
+
+        render_numbered(&mut r, Some(0))?;
+        assert_eq!(r.writer().lines(), ["line 0", "line 1", "line 2"]);
+        assert_eq!(r.scroll_offset(), 0);
+
+        r.scroll_to(100);
+        render_numbered(&mut r, None)?;
+        assert_eq!(r.writer().lines(), ["line 2", "line 3", "line 4"]);
+        assert_eq!(r.scroll_offset(), 2);
+        assert!(r.writer().scrollback().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn viewport_without_follow_cursor() -> Result<()> {
+        let mut r = Screen::new(20, 3).into_renderer().with_follow_cursor(false);
+        r.scroll_to(1);
+        render_numbered(&mut r, Some(0))?;
+        assert_eq!(r.writer().lines(), ["line 1", "line 2", "line 3"]);
+        assert!(!r.writer().cursor_visible());
+        Ok(())
+    }
+
+    #[test]
+    fn viewport_full_screen() -> Result<()> {
+        let mut r = Screen::new(20, 3)
+            .into_renderer()
+            .with_mode(Mode::FullScreen);
+        render_numbered(&mut r, Some(4))?;
+        assert_eq!(r.writer().lines(), ["line 2", "line 3", "line 4"]);
+        assert_eq!(r.writer().cursor(), (6, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn status_bar() -> io::Result<()> {
+        let mut r = Renderer::new(vec![])
+            .with_mode(Mode::StatusBar { rows: 1 })
+            .with_size_source(|| Some((20, 4)));
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        assert_eq!(
+            r.writer,
+            b"\n\x1b[1A\x1b7\x1b[1;3r\x1b[4;1H\x1b[J\x1b8\x1b7\x1b[4;1Htrans rights\x1b[K\x1b8",
+        );
+
+        r.writer.clear();
+        r.reset()?
+            .render(("enby rights".into_element(), Cursor))?
+            .finish()?;
+        assert_eq!(r.writer, b"\x1b7\x1b[4;1Henby rights\x1b[K\x1b8");
+
+        r.writer.clear();
+        r.clear()?;
+        assert_eq!(r.writer, b"\x1b7\x1b[4;1H\x1b[J\x1b[r\x1b8");
+        Ok(())
+    }
+
+    #[test]
+    fn status_bar_screen() -> Result<()> {
+        let mut screen = Screen::new(20, 4);
+        write!(screen, "$ prompt\r\n")?;
+        let mut r = screen
+            .into_renderer()
+            .with_mode(Mode::StatusBar { rows: 2 });
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        for line in ["one", "two", "three"] {
+            r.print(line)?;
+        }
+        let screen = r.writer();
+        assert_eq!(screen.lines(), ["three", "", "trans rights", "enby rights"]);
+        assert_eq!(screen.scrollback(), ["$ prompt", "one", "two"]);
+        assert_eq!(screen.cursor(), (0, 1));
+
+        r.leave()?;
+        let screen = r.writer();
+        assert_eq!(screen.lines(), ["three", "trans rights", "enby rights", ""]);
+        assert_eq!(screen.cursor(), (0, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn full_screen_wide_cursor() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]).with_mode(Mode::FullScreen);
+        let line = "x".repeat(70_000);
+        r.reset()?
+            .render((line.as_str().into_element(), Cursor))?
+            .finish()?;
+        assert!(r.writer.ends_with(b"\x1b[1;65535H\x1b[?25h"));
+        Ok(())
+    }
+
+    #[test]
+    fn status_bar_start_column() -> Result<()> {
+        let mut screen = Screen::new(20, 4);
+        write!(screen, "$ e")?;
+        let mut r = screen
+            .into_renderer()
+            .with_mode(Mode::StatusBar { rows: 1 })
+            .with_start_column(3);
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        r.print("p1")?;
+        r.print("p2")?;
+        let screen = r.writer();
+        assert_eq!(screen.lines(), ["p1", "p2", "", "trans rights"]);
+        assert_eq!(screen.scrollback(), ["$ e"]);
+        assert_eq!(screen.cursor(), (0, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn status_bar_without_size() -> io::Result<()> {
+        let mut r = Renderer::new(vec![]).with_mode(Mode::StatusBar { rows: 1 });
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        r.clear()?;
+        assert_eq!(r.writer, b"");
+        Ok(())
+    }
+
+    #[test]
+    fn start_column() -> io::Result<()> {
+        let mut r = Renderer::new(vec![])
+            .with_start_column(2)
+            .with_size_source(|| Some((10, 24)));
+        r.reset()?
+            .render(("trans ".into_element(), Cursor, "rights".into_element()))?
+            .render("enby rights".into_element())?
+            .finish()?;
+        assert_eq!(
+            r.writer,
+            b"\r\x1b[2Ctrans ri\n\renby right\x1b[1A\r\x1b[8C\x1b[?25h",
+        );
+
+        r.writer.clear();
+        r.reset()?
+            .render("enby".into_element())?
+            .render(("trans".into_element(), Cursor))?
+            .finish()?;
+        assert_eq!(r.writer, b"\r\x1b[2Cenby\x1b[K\x1b[1B\rtrans\x1b[K",);
+
+        r.writer.clear();
+        r.clear()?;
+        assert_eq!(r.writer, b"\x1b[1A\r\x1b[2C\x1b[J");
+        Ok(())
+    }
+
+    #[test]
+    fn render_in_terminal_width() -> Result<()> {
+        let mut screen = Screen::new(10, 3);
+        write!(screen, "$ ")?;
+        let mut r = screen.into_renderer().with_start_column(2);
+        r.reset()?
+            .render(Abbreviated("abcdefghij", "abc"))?
+            .render(Abbreviated("abcdefghij", "abc"))?
+            .render(Abbreviated("abcdefghijk", "abc"))?
+            .finish()?;
+        let screen = r.writer();
+        assert_eq!(screen.lines(), ["$ abc", "abcdefghij", "abc"]);
+        Ok(())
+    }
+
+    #[test]
+    fn start_column_screen() -> Result<()> {
+        let mut screen = Screen::new(20, 4);
+        write!(screen, "$ prompt ")?;
+        let mut r = screen.into_renderer().with_start_column(9);
+        r.reset()?
+            .render(("completion".into_element(), Cursor))?
+            .render("suggestion".into_element())?
+            .finish()?;
+        let screen = r.writer();
+        assert_eq!(
+            screen.lines(),
+            ["$ prompt completion", "suggestion", "", ""]
+        );
+        assert_eq!(screen.cursor(), (19, 0));
+
+        r.clear()?;
+        let screen = r.writer();
+        assert_eq!(screen.lines(), ["$ prompt", "", "", ""]);
+        assert_eq!(screen.cursor(), (9, 0));
+
+        r.reset()?.render("status".into_element())?.finish()?;
+        r.print("hello")?;
+        let screen = r.writer();
+        assert_eq!(screen.lines(), ["$ prompt hello", "status", "", ""]);
+        Ok(())
+    }
+
+    #[test]
+    fn start_column_resize() -> Result<()> {
+        let size = Arc::new(Mutex::new((20, 24)));
+        let mut r = Renderer::new(vec![])
+            .with_start_column(12)
+            .with_size_source({
+                let size = size.clone();
+                move || Some(*size.lock().unwrap())
+            });
+        r.reset()?.render("trans".into_element())?.finish()?;
+        r.writer.clear();
+        // The text before the first line is rewrapped onto two rows, so the
+        // first line now starts at column 2 of the second row.
+        *size.lock().unwrap() = (10, 24);
+        assert!(r.check_size()?);
+        assert_eq!(r.writer, b"\r\x1b[2C\x1b[Jtrans\x1b[K");
+        Ok(())
+    }
+
+    #[test]
+    fn restore_sequence() -> Result<()> {
+        let mut screen = Screen::new(20, 4);
+        write!(screen, "$ prompt\r\n")?;
+        let mut r = screen.into_renderer();
+        r.reset()?
+            .render("trans rights".styled(Style::BOLD))?
+            .render(("enby rights".into_element(), Cursor))?
+            .finish()?;
+        let sequence = r.restore_sequence()?;
+        assert_eq!(sequence, b"\x1b[1A\r\x1b[J\x1b[m\x1b[?25h");
+
+        let mut screen = r.writer().clone();
+        screen.write_all(&sequence)?;
+        assert_eq!(screen.lines(), ["$ prompt", "", "", ""]);
+        assert_eq!(screen.cursor(), (0, 1));
+        assert!(screen.cursor_visible());
+        Ok(())
+    }
+
+    #[test]
+    fn restore_sequence_full_screen() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]).with_mode(Mode::FullScreen);
+        assert_eq!(r.restore_sequence()?, b"\x1b[m\x1b[?25h");
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        assert_eq!(r.restore_sequence()?, b"\x1b[?1049l\x1b[m\x1b[?25h");
+        Ok(())
+    }
+
+    #[test]
+    fn suspend_resume() -> Result<()> {
+        let mut screen = Screen::new(20, 4);
+        write!(screen, "$ prompt\r\n")?;
+        let mut r = screen.into_renderer();
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render(("enby rights".into_element(), Cursor))?
+            .finish()?;
+        r.suspend()?;
+        let screen = r.writer();
+        assert_eq!(screen.lines(), ["$ prompt", "", "", ""]);
+        assert!(screen.cursor_visible());
+
+        write!(r.writer_mut(), "$ vim\r\n")?;
+        r.resume()?;
+        let screen = r.writer();
+        assert_eq!(
+            screen.lines(),
+            ["$ prompt", "$ vim", "trans rights", "enby rights"]
+        );
+        assert_eq!(screen.cursor(), (11, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn run_external() -> Result<()> {
+        struct Raw {
+            screen: Screen,
+            raw: Arc<Mutex<bool>>,
+        }
+
+        impl Write for Raw {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.screen.write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                self.screen.flush()
+            }
+        }
+
+        impl RawMode for Raw {
+            fn suspend_raw_mode(&self) -> io::Result<()> {
+                *self.raw.lock().unwrap() = false;
+                Ok(())
+            }
+
+            fn activate_raw_mode(&self) -> io::Result<()> {
+                *self.raw.lock().unwrap() = true;
+                Ok(())
+            }
+        }
+
+        let raw = Arc::new(Mutex::new(true));
+        let mut r = Renderer::new(Raw {
+            screen: Screen::new(20, 4),
+            raw: raw.clone(),
+        })
+        .with_size_source(|| Some((20, 4)));
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        let value = r.run_external(|| {
+            assert!(!*raw.lock().unwrap());
+            42
+        })?;
+        assert_eq!(value, 42);
+        assert!(*raw.lock().unwrap());
+        let screen = &r.writer().screen;
+        assert_eq!(screen.lines(), ["trans rights", "", "", ""]);
+        assert!(!screen.cursor_visible());
+        Ok(())
+    }
+
+    #[test]
+    fn cursor_shape() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        let shaped = Cursor.with_shape(CursorShape::SteadyBar);
+        r.reset()?
+            .render(("trans rights".into_element(), shaped))?
+            .finish()?;
+        assert_eq!(r.writer, b"\rtrans rights\x1b[K\x1b[6 q\x1b[?25h");
+
+        r.writer.clear();
+        r.reset()?
+            .render(("enby rights".into_element(), shaped))?
+            .finish()?;
+        assert_eq!(r.writer, b"\renby rights\x1b[K");
+
+        r.writer.clear();
+        r.reset()?
+            .render(("enby".into_element(), Cursor))?
+            .finish()?;
+        assert_eq!(r.writer, b"\renby\x1b[K\x1b[0 q");
+
+        r.writer.clear();
+        let shaped = Cursor.with_shape(CursorShape::BlinkingUnderline);
+        r.reset()?.render(shaped)?.finish()?;
+        r.clear()?;
+        assert_eq!(r.writer, b"\r\x1b[K\x1b[3 q\x1b[0 q\x1b[J",);
+        Ok(())
+    }
+
+    #[test]
+    fn cursor_shape_screen() -> Result<()> {
+        let mut r = Screen::new(20, 4).into_renderer();
+        let shaped = Cursor.with_shape(CursorShape::BlinkingBar);
+        r.reset()?
+            .render(("trans rights".into_element(), shaped))?
+            .finish()?;
+        assert_eq!(r.writer().cursor_shape(), Some(CursorShape::BlinkingBar));
+        r.clear()?;
+        assert_eq!(r.writer().cursor_shape(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn render_block() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        let block = vec!["trans rights".into_element(), "enby rights".into_element()];
+        r.reset()?
+            .render_block(&block)?
+            .render("gay rights".into_element())?
+            .finish()?;
+        assert_eq!(
+            r.writer,
+            b"\rtrans rights\x1b[K\n\renby rights\x1b[K\n\rgay rights\x1b[K\x1b[?25l",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn suspend_unfinished() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?.render("trans rights".into_element())?;
+        assert!(matches!(r.suspend(), Err(Error::Unfinished)));
+        assert!(matches!(r.resume(), Err(Error::Unfinished)));
+        Ok(())
+    }
+
+    #[test]
+    fn single_write() -> io::Result<()> {
+        struct Writes(Vec<Vec<u8>>);
+
+        impl Write for Writes {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.push(buf.to_vec());
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut r = Renderer::for_test(Writes(vec![]));
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .render("enby rights".styled(Style::BOLD))?
+            .finish()?;
+        assert_eq!(
+            r.writer.0,
+            [b"\rtrans rights\x1b[K\n\r\x1b[1menby rights\x1b[m\x1b[K\x1b[1A\r\x1b[12C\x1b[?25h"],
+        );
+
+        // Nothing is written if nothing has changed.
+        r.reset()?
+            .render(("trans rights".into_element(), Cursor))?
+            .render("enby rights".styled(Style::BOLD))?
+            .finish()?;
+        assert_eq!(r.writer.0.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn synchronized_output() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]).with_synchronized_output(true);
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        assert_eq!(
+            r.writer,
+            b"\x1b[?2026h\rtrans rights\x1b[K\x1b[?25l\x1b[?2026l",
+        );
+
+        r.writer.clear();
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        assert_eq!(r.writer, b"");
+
+        r.print("enby rights")?;
+        assert_eq!(
+            r.writer,
+            b"\x1b[?2026h\r\x1b[Jenby rights\n\rtrans rights\x1b[K\x1b[?2026l",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn plain() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]).with_mode(Mode::Plain);
+        r.reset()?
+            .render("trans rights".styled(Style::BOLD))?
+            .render(("enby ".into_element(), Cursor, "rights".into_element()))?
+            .finish()?;
+        r.flush()?;
+        assert_eq!(r.writer, b"");
+
+        r.print(("hello".styled(Style::fg(1)), Cursor))?;
+        assert_eq!(r.writer, b"hello\n");
+
+        r.writer.clear();
+        r.leave()?;
+        assert_eq!(r.writer, b"trans rights\nenby rights\n");
+
+        r.writer.clear();
+        r.reset()?.render("human rights".into_element())?.finish()?;
+        r.clear()?;
+        assert_eq!(r.writer, b"");
+
+        // Lines wider than the terminal are not truncated.
+        let mut r = Renderer::new(vec![])
+            .with_mode(Mode::Plain)
+            .with_size_source(|| Some((4, 24)));
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        r.leave()?;
+        r.print("enby rights")?;
+        assert_eq!(r.writer, b"trans rights\nenby rights\n");
+        Ok(())
+    }
+
+    #[test]
+    fn plain_drop() {
+        let mut output = vec![];
+        let mut r = Renderer::for_test(&mut output).with_mode(Mode::Plain);
+        let _ = r
+            .reset()
+            .and_then(|r| r.render("trans rights".into_element()));
+        let _ = r.finish();
+        std::mem::drop(r);
+        assert_eq!(output, b"");
+    }
+
+    #[test]
+    fn detect_tty() {
+        let file = std::fs::File::open("/dev/null").unwrap();
+        let r = Renderer::new(file).detect_tty();
+        assert_eq!(r.mode, Mode::Plain);
+    }
+
+    #[test]
+    fn frame() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        let mut frame = r.frame()?;
+        frame
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?;
+        frame.finish()?;
+        assert_eq!(
+            r.writer,
+            b"\rtrans rights\x1b[K\n\renby rights\x1b[K\x1b[?25l",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn frame_finished_on_drop() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.frame()?.render("trans rights".into_element())?;
+        assert_eq!(r.writer, b"\rtrans rights\x1b[K\x1b[?25l");
+        r.writer.clear();
+        r.clear()?;
+        assert_eq!(r.writer, b"\r\x1b[J\x1b[?25h");
+        Ok(())
+    }
+
+    #[test]
+    fn draw() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        let value = r.draw(|frame| {
+            frame.render(("trans rights".into_element(), Cursor))?;
+            Ok(42)
+        })?;
+        assert_eq!(value, 42);
+        assert_eq!(r.writer, b"\rtrans rights\x1b[K\x1b[?25h");
+        Ok(())
+    }
+
+    #[test]
+    fn draw_error() {
+        let mut r = Renderer::for_test(vec![]);
+        let result = r.draw(|frame| {
+            frame.render("trans rights".into_element())?;
+            Err::<(), _>(io::Error::other("oops").into())
+        });
+        assert_eq!(result.unwrap_err().to_string(), "oops");
+        assert_eq!(r.writer, b"\rtrans rights\x1b[K\x1b[?25l");
+        r.clear().unwrap();
+    }
+
+    #[test]
+    fn leave_empty() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?.finish()?;
+        r.writer.clear();
+        r.leave()?;
+        assert_eq!(r.writer, b"");
+        Ok(())
+    }
+
+    #[test]
     fn leave() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
+        let mut r = Renderer::for_test(vec![]);
         r.reset()?
             .render("trans rights".into_element())?
             .render("enby rights".into_element())?
@@ -321,13 +2664,13 @@ mod tests {
         r.writer.clear();
         r.leave()?;
         r.clear()?;
-        assert_eq!(r.writer, b"\n\r\r\x1b[J\x1b[?25h");
+        assert_eq!(r.writer, b"\n\r\x1b[J\x1b[?25h");
         Ok(())
     }
 
     #[test]
     fn leave_with_cursor() -> io::Result<()> {
-        let mut r = Renderer::new(vec![]);
+        let mut r = Renderer::for_test(vec![]);
         r.reset()?
             .render(("trans rights".into_element(), Cursor))?
             .render("enby rights".into_element())?
@@ -335,7 +2678,43 @@ mod tests {
         r.writer.clear();
         r.leave()?;
         r.clear()?;
-        assert_eq!(r.writer, b"\x1b[1B\n\r\r\x1b[J\x1b[?25h");
+        assert_eq!(r.writer, b"\x1b[1B\n\r\x1b[J");
+        Ok(())
+    }
+
+    #[test]
+    fn unfinished() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?.render("trans rights".into_element())?;
+        assert!(matches!(r.reset(), Err(Error::Unfinished)));
+        assert!(matches!(r.flush(), Err(Error::Unfinished)));
+        assert!(matches!(r.print("hello"), Err(Error::Unfinished)));
+        assert!(matches!(r.leave(), Err(Error::Unfinished)));
+        assert!(matches!(r.clear(), Err(Error::Unfinished)));
+        assert_eq!(r.writer, b"");
+
+        r.finish()?;
+        r.reset()?;
+        Ok(())
+    }
+
+    #[test]
+    fn drop_unfinished() {
+        let mut output = vec![];
+        let mut r = Renderer::for_test(&mut output);
+        let _ = r.render("trans rights".into_element());
+        std::mem::drop(r);
+        assert_eq!(output, b"\r\x1b[J\x1b[?25h");
+    }
+
+    #[test]
+    fn too_many_lines() -> io::Result<()> {
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?;
+        for _ in 0..u16::MAX {
+            r.render(())?;
+        }
+        assert!(matches!(r.render(()), Err(Error::TooManyLines)));
         Ok(())
     }
 
@@ -343,11 +2722,12 @@ mod tests {
     fn no_drop_during_panic() {
         let mut output = vec![];
         let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
-            let mut r = Renderer::new(&mut output);
-            let _ = r.render("hello".into_element());
+            let mut r = Renderer::for_test(&mut output);
+            let _ = r.render("hello".into_element()).and_then(|r| r.finish());
+            let _ = r.render("world".into_element());
             panic!();
         }));
         result.unwrap_err();
-        assert_eq!(output, b"hello\x1b[m\x1b[K");
+        assert_eq!(output, b"\rhello\x1b[K\x1b[?25l");
     }
 }
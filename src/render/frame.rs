@@ -0,0 +1,75 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+use std::io::Write;
+
+use crate::block::Block;
+use crate::element::Element;
+use crate::{Renderer, Result};
+
+/// A frame that is being rendered, created by [`Renderer::frame`].
+///
+/// The frame is [finished](Renderer::finish) when it is dropped, so it is not
+/// possible to forget to finish it. Since the frame borrows the `Renderer`,
+/// no other methods of the `Renderer` can be called while it exists.
+///
+/// # Example
+///
+/// ```
+/// use line_ui::Renderer;
+/// use line_ui::element::IntoElement;
+///
+/// # fn main() -> std::io::Result<()> {
+/// let mut r = Renderer::new(vec![]);
+/// let mut frame = r.frame()?;
+/// for line in ["one", "two", "three"] {
+///     frame.render(line.into_element())?;
+/// }
+/// frame.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Frame<'r, W: Write> {
+    renderer: &'r mut Renderer<W>,
+    finished: bool,
+}
+
+impl<'r, W: Write> Frame<'r, W> {
+    pub(super) fn new(renderer: &'r mut Renderer<W>) -> Result<Self> {
+        renderer.reset()?;
+        Ok(Frame {
+            renderer,
+            finished: false,
+        })
+    }
+
+    /// Renders a line. See [`Renderer::render`].
+    pub fn render<'s, E: Element<'s>>(&mut self, line: E) -> Result<&mut Self> {
+        self.renderer.render(line)?;
+        Ok(self)
+    }
+
+    /// Renders each line of a block. See [`Renderer::render_block`].
+    pub fn render_block<'s, B: Block<'s>>(&mut self, block: B) -> Result<&mut Self> {
+        self.renderer.render_block(block)?;
+        Ok(self)
+    }
+
+    /// Finishes the frame. See [`Renderer::finish`].
+    ///
+    /// This is equivalent to dropping the frame, except that errors are
+    /// reported.
+    pub fn finish(mut self) -> Result<()> {
+        self.finished = true;
+        self.renderer.finish()
+    }
+}
+
+impl<W: Write> Drop for Frame<'_, W> {
+    fn drop(&mut self) {
+        if !self.finished && !std::thread::panicking() {
+            let _ = self.renderer.finish();
+        }
+    }
+}
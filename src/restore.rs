@@ -0,0 +1,283 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+//! Restoring the terminal when the program crashes.
+//!
+//! A [`Renderer`](crate::Renderer) hides the cursor and draws lines that it
+//! expects to clear later, but it does not clean up when dropped during a
+//! panic. Once [`install_panic_hook`] has been called, each renderer that was
+//! registered with [`with_restore`](crate::Renderer::with_restore) keeps track
+//! of the output needed to restore the terminal, which is written to the
+//! renderer's own output before the panic message is printed. With the
+//! `signals` feature,
+#![cfg_attr(
+    feature = "signals",
+    doc = "[`signal::restore_on_termination`](crate::signal::restore_on_termination)"
+)]
+#![cfg_attr(not(feature = "signals"), doc = "`signal::restore_on_termination`")]
+//! does the same when the process is interrupted or terminated.
+//!
+//! The terminal is restored by clearing the rendered lines, leaving the
+//! alternate screen and scroll region, showing the cursor, and resetting the
+//! style. The panic hook does not affect raw mode, which a
+//! [`Session`](crate::session::Session) disables when it is dropped while
+//! unwinding. A signal terminates the process without unwinding, so
+#![cfg_attr(
+    feature = "signals",
+    doc = "[`signal::restore_on_termination`](crate::signal::restore_on_termination)"
+)]
+#![cfg_attr(not(feature = "signals"), doc = "`signal::restore_on_termination`")]
+//! also restores the original mode of each session's terminal.
+//!
+//! A [`Session`](crate::session::Session) registers its renderer
+//! automatically.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use line_ui::Renderer;
+//! use line_ui::element::IntoElement;
+//!
+//! # fn main() -> std::io::Result<()> {
+//! line_ui::restore::install_panic_hook();
+//! let mut r = Renderer::new(std::io::stdout()).with_restore()?;
+//! r.reset()?.render("working...".into_element())?.finish()?;
+//! panic!("the cursor is shown and the line cleared before this message");
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Write};
+#[cfg(feature = "signals")]
+use std::os::fd::AsRawFd;
+use std::os::fd::BorrowedFd;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, Once, PoisonError};
+
+/// Whether renderers should keep their restore sequences up to date.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The identifier to assign to the next renderer or saved mode.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A registered renderer.
+struct Entry {
+    /// A duplicate of the file descriptor that the renderer writes to.
+    output: File,
+    /// The output that restores the terminal, or nothing if the renderer has
+    /// not drawn anything.
+    sequence: Vec<u8>,
+}
+
+/// The registered renderers.
+static ENTRIES: Mutex<BTreeMap<u64, Entry>> = Mutex::new(BTreeMap::new());
+
+fn entries() -> MutexGuard<'static, BTreeMap<u64, Entry>> {
+    ENTRIES.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The original mode of a terminal that has been put into raw mode.
+#[cfg(feature = "signals")]
+struct SavedMode {
+    /// A duplicate of the terminal's file descriptor.
+    terminal: File,
+    termios: libc::termios,
+}
+
+/// The saved modes of terminals, which are restored by [`restore_modes`].
+#[cfg(feature = "signals")]
+static MODES: Mutex<BTreeMap<u64, SavedMode>> = Mutex::new(BTreeMap::new());
+
+#[cfg(feature = "signals")]
+fn modes() -> MutexGuard<'static, BTreeMap<u64, SavedMode>> {
+    MODES.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Makes renderers keep track of how to restore the terminal.
+pub(crate) fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns whether renderers should keep track of how to restore the
+/// terminal.
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Registers a renderer that writes to the given file descriptor, returning
+/// its identifier.
+pub(crate) fn register(fd: BorrowedFd) -> io::Result<u64> {
+    let output = File::from(fd.try_clone_to_owned()?);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let entry = Entry {
+        output,
+        sequence: Vec::new(),
+    };
+    entries().insert(id, entry);
+    Ok(id)
+}
+
+/// Sets the output that restores the terminal for the given renderer.
+pub(crate) fn update(id: u64, sequence: Vec<u8>) {
+    if let Some(entry) = entries().get_mut(&id) {
+        entry.sequence = sequence;
+    }
+}
+
+/// Forgets the given renderer.
+pub(crate) fn remove(id: u64) {
+    entries().remove(&id);
+}
+
+/// Saves the current mode of the given terminal, before it is put into raw
+/// mode, returning an identifier for it.
+#[cfg(feature = "signals")]
+pub(crate) fn save_mode(fd: BorrowedFd) -> io::Result<u64> {
+    let terminal = File::from(fd.try_clone_to_owned()?);
+    // SAFETY: `termios` is a plain C struct, for which all zeroes is valid,
+    // and it is only read once `tcgetattr` has filled it in.
+    let mut termios = unsafe { std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(terminal.as_raw_fd(), &mut termios) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    modes().insert(id, SavedMode { terminal, termios });
+    Ok(id)
+}
+
+/// Forgets the saved mode of a terminal, once it has been restored.
+#[cfg(feature = "signals")]
+pub(crate) fn forget_mode(id: u64) {
+    modes().remove(&id);
+}
+
+/// Restores the saved mode of each terminal, e.g., disabling raw mode.
+#[cfg(feature = "signals")]
+pub(crate) fn restore_modes() -> io::Result<()> {
+    let mut result = Ok(());
+    for mode in std::mem::take(&mut *modes()).values().rev() {
+        let fd = mode.terminal.as_raw_fd();
+        // SAFETY: `fd` is open for as long as `mode.terminal` is.
+        if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &mode.termios) } != 0 {
+            result = Err(io::Error::last_os_error());
+        }
+    }
+    result
+}
+
+/// Installs a panic hook that restores the terminal before the panic message
+/// is printed. The previous panic hook is called afterwards.
+///
+/// This only needs to be called once; subsequent calls have no effect.
+pub fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        enable();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let _ = restore_terminal();
+            previous(info);
+        }));
+    });
+}
+
+/// Restores the terminal by writing the output needed to clean up after each
+/// registered renderer to that renderer's output.
+///
+/// This only has an effect once [`install_panic_hook`] or
+#[cfg_attr(
+    feature = "signals",
+    doc = "[`signal::restore_on_termination`](crate::signal::restore_on_termination)"
+)]
+#[cfg_attr(not(feature = "signals"), doc = "`signal::restore_on_termination`")]
+/// has been called. If the program continues to use a renderer afterwards, it
+/// may draw incorrectly, since it no longer knows what is displayed.
+pub fn restore_terminal() -> io::Result<()> {
+    let mut entries = entries();
+    let mut result = Ok(());
+    // The most recently created renderer is cleaned up first.
+    for entry in entries.values_mut().rev() {
+        let sequence = std::mem::take(&mut entry.sequence);
+        if !sequence.is_empty() {
+            let written = (&entry.output).write_all(&sequence);
+            result = result.and(written);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use crate::Renderer;
+    use crate::element::IntoElement;
+
+    use super::*;
+
+    #[test]
+    fn registration() -> crate::Result<()> {
+        enable();
+        let (mut reader, writer) = io::pipe()?;
+        let mut r = Renderer::new(writer).with_unknown_size().with_restore()?;
+        let id = r.restore_id.unwrap();
+        assert_eq!(entries()[&id].sequence, b"");
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        assert_eq!(entries()[&id].sequence, b"\r\x1b[J\x1b[m\x1b[?25h");
+
+        restore_terminal()?;
+        assert_eq!(entries()[&id].sequence, b"");
+        drop(r);
+        assert!(!entries().contains_key(&id));
+
+        let mut output = Vec::new();
+        reader.read_to_end(&mut output)?;
+        assert!(output.starts_with(b"\rtrans rights\x1b[K\x1b[?25l\r\x1b[J\x1b[m\x1b[?25h"));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "signals")]
+    fn saved_mode() -> io::Result<()> {
+        use std::os::fd::{AsFd, FromRawFd, OwnedFd};
+
+        use termion::raw::IntoRawMode;
+
+        fn lflag(terminal: &File) -> libc::tcflag_t {
+            let mut termios = unsafe { std::mem::zeroed() };
+            assert_eq!(
+                unsafe { libc::tcgetattr(terminal.as_raw_fd(), &mut termios) },
+                0
+            );
+            termios.c_lflag
+        }
+
+        let (mut controller, mut terminal) = (-1, -1);
+        let (name, termp, winp) = (std::ptr::null_mut(), std::ptr::null(), std::ptr::null());
+        let result = unsafe { libc::openpty(&mut controller, &mut terminal, name, termp, winp) };
+        assert_eq!(result, 0);
+        let _controller = unsafe { OwnedFd::from_raw_fd(controller) };
+        let terminal = File::from(unsafe { OwnedFd::from_raw_fd(terminal) });
+        let original = lflag(&terminal);
+
+        let id = save_mode(terminal.as_fd())?;
+        let raw = terminal.try_clone()?.into_raw_mode()?;
+        assert_ne!(lflag(&terminal), original);
+        restore_modes()?;
+        assert_eq!(lflag(&terminal), original);
+        assert!(!modes().contains_key(&id));
+        drop(raw);
+        Ok(())
+    }
+
+    #[test]
+    fn no_registration() -> crate::Result<()> {
+        enable();
+        let mut r = Renderer::for_test(vec![]);
+        r.reset()?.render("trans rights".into_element())?.finish()?;
+        assert_eq!(r.restore_id, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,366 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+//! Managing the terminal for the lifetime of a UI.
+//!
+//! A [`Session`] puts the terminal into raw mode, and owns a [`Renderer`] that
+//! draws to it. When the session is dropped, the rendered lines are cleared
+//! before the terminal is returned to its original mode.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use line_ui::element::IntoElement;
+//! use line_ui::session::Session;
+//! use termion::input::TermRead;
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let mut session = Session::new()?;
+//! session
+//!     .renderer()
+//!     .reset()?
+//!     .render("Press any key to continue!".into_element())?
+//!     .finish()?;
+//! let _ = std::io::stdin().events().next();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::File;
+use std::io::{self, Stdout, Write};
+use std::os::fd::{AsFd, BorrowedFd};
+
+use termion::raw::{IntoRawMode, RawTerminal};
+
+use crate::{Mode, Renderer, Result};
+
+/// A writer to a terminal whose raw mode can be temporarily disabled.
+///
+/// This is used by [`Renderer::run_external`] to hand the terminal over to
+/// another program.
+pub trait RawMode {
+    /// Temporarily restores the terminal's original mode.
+    fn suspend_raw_mode(&self) -> io::Result<()>;
+
+    /// Enables raw mode again after
+    /// [`suspend_raw_mode`](Self::suspend_raw_mode).
+    fn activate_raw_mode(&self) -> io::Result<()>;
+}
+
+impl<W: Write + AsFd> RawMode for RawTerminal<W> {
+    fn suspend_raw_mode(&self) -> io::Result<()> {
+        RawTerminal::suspend_raw_mode(self)
+    }
+
+    fn activate_raw_mode(&self) -> io::Result<()> {
+        RawTerminal::activate_raw_mode(self)
+    }
+}
+
+/// The file that a [`Terminal`] writes to.
+enum Output {
+    Stdout(Stdout),
+    Tty(File),
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Stdout(stdout) => stdout.write(buf),
+            Output::Tty(file) => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Stdout(stdout) => stdout.flush(),
+            Output::Tty(file) => file.flush(),
+        }
+    }
+}
+
+impl AsFd for Output {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        match self {
+            Output::Stdout(stdout) => stdout.as_fd(),
+            Output::Tty(file) => file.as_fd(),
+        }
+    }
+}
+
+/// The writer of a [`Session`]'s renderer: either standard output or the
+/// controlling terminal, in raw mode if it is a terminal.
+pub struct Terminal {
+    inner: TerminalInner,
+}
+
+enum TerminalInner {
+    Raw(RawTerminal<Output>),
+    Plain(Output),
+}
+
+impl Terminal {
+    /// Returns whether the terminal is in raw mode, i.e., whether it is a
+    /// terminal at all.
+    pub fn is_raw(&self) -> bool {
+        matches!(self.inner, TerminalInner::Raw(_))
+    }
+}
+
+impl RawMode for Terminal {
+    fn suspend_raw_mode(&self) -> io::Result<()> {
+        match &self.inner {
+            TerminalInner::Raw(raw) => raw.suspend_raw_mode(),
+            TerminalInner::Plain(_) => Ok(()),
+        }
+    }
+
+    fn activate_raw_mode(&self) -> io::Result<()> {
+        match &self.inner {
+            TerminalInner::Raw(raw) => raw.activate_raw_mode(),
+            TerminalInner::Plain(_) => Ok(()),
+        }
+    }
+}
+
+impl Write for Terminal {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            TerminalInner::Raw(raw) => raw.write(buf),
+            TerminalInner::Plain(output) => output.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            TerminalInner::Raw(raw) => raw.flush(),
+            TerminalInner::Plain(output) => output.flush(),
+        }
+    }
+}
+
+impl AsFd for Terminal {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        match &self.inner {
+            TerminalInner::Raw(raw) => raw.as_fd(),
+            TerminalInner::Plain(output) => output.as_fd(),
+        }
+    }
+}
+
+/// A [`Renderer`] that owns the terminal, keeping it in raw mode.
+///
+/// When the session is dropped, the renderer is [cleared](Renderer::clear),
+/// and then the terminal's original mode is restored. To keep the rendered
+/// lines instead, call [`leave`](Renderer::leave) first.
+///
+/// If the session is on a terminal, its renderer is registered with
+/// [`with_restore`](Renderer::with_restore), so that it is cleaned up by
+/// [`restore_terminal`](crate::restore::restore_terminal).
+///
+/// See the [module-level documentation](self) for an example.
+pub struct Session {
+    // The renderer clears its lines when dropped, before the terminal it owns
+    // is dropped and restored to its original mode.
+    renderer: Renderer<Terminal>,
+    /// The identifier of the terminal's original mode, which is saved so that
+    /// it can be [restored](crate::restore) if the process is terminated.
+    #[cfg(feature = "signals")]
+    saved_mode: Option<u64>,
+}
+
+impl Session {
+    /// Starts a session on standard output.
+    ///
+    /// If standard output is not a terminal, raw mode is not enabled, and the
+    /// renderer uses [`Mode::Plain`].
+    pub fn new() -> io::Result<Self> {
+        Session::open(Output::Stdout(io::stdout()))
+    }
+
+    /// Starts a session on standard output if it is a terminal, or on the
+    /// controlling terminal (`/dev/tty`) otherwise. This way, the UI is
+    /// displayed even if standard output is piped to another program.
+    ///
+    /// Returns an error if the process has no controlling terminal.
+    pub fn new_on_tty() -> io::Result<Self> {
+        let stdout = io::stdout();
+        if termion::is_tty(&stdout.as_fd()) {
+            Session::open(Output::Stdout(stdout))
+        } else {
+            Session::open(Output::Tty(termion::get_tty()?))
+        }
+    }
+
+    fn open(output: Output) -> io::Result<Self> {
+        if !termion::is_tty(&output.as_fd()) {
+            let inner = TerminalInner::Plain(output);
+            let renderer = Renderer::new(Terminal { inner }).with_mode(Mode::Plain);
+            return Ok(Session {
+                renderer,
+                #[cfg(feature = "signals")]
+                saved_mode: None,
+            });
+        }
+        // The size of the terminal is queried from the file being written,
+        // which may not be standard output.
+        let size_file = match &output {
+            Output::Stdout(_) => None,
+            Output::Tty(file) => Some(file.try_clone()?),
+        };
+        #[cfg(feature = "signals")]
+        let saved_mode = crate::restore::save_mode(output.as_fd())?;
+        let raw = output.into_raw_mode();
+        #[cfg(feature = "signals")]
+        if raw.is_err() {
+            crate::restore::forget_mode(saved_mode);
+        }
+        let inner = TerminalInner::Raw(raw?);
+        let mut renderer = Renderer::new(Terminal { inner }).with_restore()?;
+        if let Some(file) = size_file {
+            renderer = renderer.with_size_source(move || termion::terminal_size_fd(&file).ok());
+        }
+        Ok(Session {
+            renderer,
+            #[cfg(feature = "signals")]
+            saved_mode: Some(saved_mode),
+        })
+    }
+
+    /// Returns the renderer.
+    pub fn renderer(&mut self) -> &mut Renderer<Terminal> {
+        &mut self.renderer
+    }
+
+    /// Clears the rendered lines and restores the terminal's original mode,
+    /// so that the process can be stopped, e.g., after the user presses
+    /// Ctrl-Z. Call [`resume`](Self::resume) once the process is continued.
+    ///
+    /// Returns [`Error::Unfinished`](crate::Error::Unfinished) if a frame is
+    /// being rendered.
+    pub fn suspend(&mut self) -> Result<()> {
+        self.renderer.suspend()?;
+        self.renderer.writer().suspend_raw_mode()?;
+        Ok(())
+    }
+
+    /// Suspends the session while running the given function, e.g., to run an
+    /// interactive program such as an editor. See
+    /// [`Renderer::run_external`].
+    pub fn run_external<T>(&mut self, f: impl FnOnce() -> T) -> Result<T> {
+        self.renderer.run_external(f)
+    }
+
+    /// Enables raw mode again after [`suspend`](Self::suspend), and redraws
+    /// the most recent frame.
+    ///
+    /// Returns [`Error::Unfinished`](crate::Error::Unfinished) if a frame is
+    /// being rendered.
+    pub fn resume(&mut self) -> Result<()> {
+        self.renderer.writer().activate_raw_mode()?;
+        self.renderer.resume()
+    }
+
+    /// Suspends the session and stops the process with `SIGTSTP`, as Ctrl-Z
+    /// would outside of raw mode. The session is resumed once the process is
+    /// continued.
+    ///
+    /// This method requires the `signals` feature.
+    #[cfg(feature = "signals")]
+    pub fn suspend_process(&mut self) -> Result<()> {
+        self.suspend()?;
+        signal_hook::low_level::raise(signal_hook::consts::SIGTSTP)?;
+        self.resume()
+    }
+}
+
+#[cfg(feature = "signals")]
+impl Drop for Session {
+    fn drop(&mut self) {
+        if let Some(id) = self.saved_mode {
+            crate::restore::forget_mode(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::os::fd::OwnedFd;
+
+    use crate::element::IntoElement;
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "signals")]
+    fn open_tty() -> Result<()> {
+        use std::os::fd::{AsRawFd, FromRawFd};
+
+        fn lflag(terminal: &File) -> libc::tcflag_t {
+            let mut termios = unsafe { std::mem::zeroed() };
+            assert_eq!(
+                unsafe { libc::tcgetattr(terminal.as_raw_fd(), &mut termios) },
+                0
+            );
+            termios.c_lflag
+        }
+
+        let (mut controller, mut terminal) = (-1, -1);
+        let size = libc::winsize {
+            ws_row: 24,
+            ws_col: 80,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        let (name, termp) = (std::ptr::null_mut(), std::ptr::null());
+        let result = unsafe { libc::openpty(&mut controller, &mut terminal, name, termp, &size) };
+        assert_eq!(result, 0);
+        let mut controller = File::from(unsafe { OwnedFd::from_raw_fd(controller) });
+        let terminal = File::from(unsafe { OwnedFd::from_raw_fd(terminal) });
+        let original = lflag(&terminal);
+
+        let mut session = Session::open(Output::Tty(terminal.try_clone()?))?;
+        assert!(session.renderer.writer.is_raw());
+        assert_eq!(lflag(&terminal) & libc::ICANON, 0);
+        assert!(session.saved_mode.is_some());
+        assert_eq!(session.renderer.size, Some((80, 24)));
+        session
+            .renderer()
+            .reset()?
+            .render("trans rights".into_element())?
+            .finish()?;
+
+        drop(session);
+        assert_eq!(lflag(&terminal), original);
+
+        // Once every copy of the terminal is closed, reading the controller
+        // fails after everything that was written has been read.
+        drop(terminal);
+        let mut output = Vec::new();
+        let _ = controller.read_to_end(&mut output);
+        assert_eq!(output, b"\rtrans rights\x1b[K\x1b[?25l\r\x1b[J\x1b[?25h",);
+        Ok(())
+    }
+
+    #[test]
+    fn open_pipe() -> Result<()> {
+        let (mut reader, writer) = io::pipe()?;
+        let mut session = Session::open(Output::Tty(File::from(OwnedFd::from(writer))))?;
+        assert!(!session.renderer.writer.is_raw());
+        assert_eq!(session.renderer.mode, Mode::Plain);
+        session
+            .renderer()
+            .reset()?
+            .render("trans rights".into_element())?
+            .finish()?;
+        session.renderer().print("enby rights".into_element())?;
+
+        drop(session);
+        let mut output = Vec::new();
+        reader.read_to_end(&mut output)?;
+        assert_eq!(output, b"enby rights\n");
+        Ok(())
+    }
+}
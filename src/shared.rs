@@ -0,0 +1,354 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+//! Rendering from multiple threads.
+//!
+//! A [`SharedRenderer`] owns a [`Renderer`], and hands out [`Handle`]s that can
+//! be sent to other threads. Each thread can then use a [`Slot`] to submit its
+//! own lines, which the owner composes into a single frame.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use line_ui::Renderer;
+//! use line_ui::shared::SharedRenderer;
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let mut shared = SharedRenderer::new(Renderer::new(vec![]));
+//!
+//! let workers: Vec<_> = (0..4)
+//!     .map(|i| {
+//!         let slot = shared.handle().slot();
+//!         std::thread::spawn(move || {
+//!             let status = format!("worker {i}: working");
+//!             slot.set(status.as_str());
+//!             slot.handle().print(format!("worker {i}: done").as_str());
+//!         })
+//!     })
+//!     .collect();
+//!
+//! while workers.iter().any(|worker| !worker.is_finished()) {
+//!     shared.wait(Duration::from_millis(50))?;
+//! }
+//! shared.update()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::element::IntoElement;
+use crate::render::Line;
+use crate::{Renderer, Result};
+
+#[derive(Default)]
+struct State {
+    /// The lines of each slot, in the order that the slots were created.
+    slots: BTreeMap<u64, Vec<Line>>,
+    next_slot: u64,
+    /// Lines that are waiting to be printed.
+    printed: Vec<Line>,
+    /// Whether anything has changed since the frame was last drawn.
+    changed: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // The state is always consistent, so a poisoned lock is harmless.
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn update(&self, f: impl FnOnce(&mut State)) {
+        let mut state = self.lock();
+        f(&mut state);
+        state.changed = true;
+        self.changed.notify_all();
+    }
+}
+
+/// A [`Renderer`] that can be updated from multiple threads.
+///
+/// See the [module-level documentation](self) for details.
+pub struct SharedRenderer<W: Write> {
+    renderer: Renderer<W>,
+    shared: Arc<Shared>,
+}
+
+impl<W: Write> SharedRenderer<W> {
+    /// Creates a new [`SharedRenderer`] that draws with the given renderer.
+    pub fn new(renderer: Renderer<W>) -> Self {
+        SharedRenderer {
+            renderer,
+            shared: Arc::default(),
+        }
+    }
+
+    /// Creates a new [`Handle`] to this renderer.
+    pub fn handle(&self) -> Handle {
+        Handle {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Returns the underlying renderer.
+    pub fn renderer(&mut self) -> &mut Renderer<W> {
+        &mut self.renderer
+    }
+
+    /// Draws the lines submitted by each [`Slot`], if anything has changed
+    /// since the last time this method was called. Lines submitted with
+    /// [`Handle::print`] are printed first.
+    ///
+    /// If the renderer has a [minimum interval](Renderer::with_min_interval),
+    /// the frame may be left pending; it is drawn by a later call to this
+    /// method once the interval has elapsed.
+    ///
+    /// Returns whether anything was rendered.
+    pub fn update(&mut self) -> Result<bool> {
+        let mut state = self.shared.lock();
+        if !state.changed {
+            drop(state);
+            if self.renderer.pending_timeout() == Some(Duration::ZERO) {
+                self.renderer.flush()?;
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+        state.changed = false;
+        let printed = std::mem::take(&mut state.printed);
+        let lines: Vec<Line> = state.slots.values().flatten().cloned().collect();
+        drop(state);
+
+        if !printed.is_empty() {
+            self.renderer.print_lines(&printed)?;
+        }
+        self.renderer.reset()?;
+        for line in &lines {
+            self.renderer.render(line)?;
+        }
+        self.renderer.finish()?;
+        Ok(true)
+    }
+
+    /// Waits until something changes or the timeout elapses, then calls
+    /// [`update`](Self::update). If a frame is pending, this waits at most
+    /// until it can be drawn.
+    pub fn wait(&mut self, timeout: Duration) -> Result<bool> {
+        let timeout = match self.renderer.pending_timeout() {
+            Some(pending_timeout) => timeout.min(pending_timeout),
+            None => timeout,
+        };
+        let state = self.shared.lock();
+        let result = self
+            .shared
+            .changed
+            .wait_timeout_while(state, timeout, |state| !state.changed);
+        drop(result);
+        self.update()
+    }
+}
+
+impl<W: Write> Drop for SharedRenderer<W> {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            // Don't lose any lines that have yet to be printed.
+            let printed = std::mem::take(&mut self.shared.lock().printed);
+            if !printed.is_empty() {
+                let _ = self.renderer.print_lines(&printed);
+            }
+        }
+    }
+}
+
+/// A handle to a [`SharedRenderer`], which can be sent to other threads.
+#[derive(Clone)]
+pub struct Handle {
+    shared: Arc<Shared>,
+}
+
+impl Handle {
+    /// Creates a new, empty [`Slot`]. The slot's lines are displayed below
+    /// those of all previously-created slots.
+    pub fn slot(&self) -> Slot {
+        let mut state = self.shared.lock();
+        let id = state.next_slot;
+        state.next_slot += 1;
+        state.slots.insert(id, Vec::new());
+        Slot {
+            shared: self.shared.clone(),
+            id,
+        }
+    }
+
+    /// Prints a line above the rendered lines. See [`Renderer::print`].
+    pub fn print<'s, E: IntoElement<'s>>(&self, line: E) {
+        self.print_lines([line]);
+    }
+
+    /// Prints multiple lines above the rendered lines. See
+    /// [`Renderer::print_lines`].
+    pub fn print_lines<'s, I>(&self, lines: I)
+    where
+        I: IntoIterator,
+        I::Item: IntoElement<'s>,
+    {
+        let lines: Vec<_> = lines
+            .into_iter()
+            .map(|line| Line::new(line.into_element()))
+            .collect();
+        self.shared.update(|state| state.printed.extend(lines));
+    }
+}
+
+/// A group of lines within a [`SharedRenderer`].
+///
+/// The slot's lines are removed when it is dropped. Note that any
+/// [`Cursor`](crate::element::Cursor)s in the lines are ignored.
+pub struct Slot {
+    shared: Arc<Shared>,
+    id: u64,
+}
+
+impl Slot {
+    /// Returns a [`Handle`] to the renderer that this slot belongs to.
+    pub fn handle(&self) -> Handle {
+        Handle {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Replaces the contents of this slot with a single line.
+    pub fn set<'s, E: IntoElement<'s>>(&self, line: E) {
+        self.set_lines([line]);
+    }
+
+    /// Replaces the contents of this slot.
+    pub fn set_lines<'s, I>(&self, lines: I)
+    where
+        I: IntoIterator,
+        I::Item: IntoElement<'s>,
+    {
+        let lines: Vec<_> = lines
+            .into_iter()
+            .map(|line| Line::new(line.into_element()))
+            .collect();
+        self.shared.update(|state| {
+            state.slots.insert(self.id, lines);
+        });
+    }
+
+    /// Removes all lines from this slot.
+    pub fn clear(&self) {
+        self.set_lines::<[(); 0]>([]);
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.shared.update(|state| {
+            state.slots.remove(&self.id);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn is_send() {
+        fn is_send<T: Send>() {}
+        is_send::<Handle>();
+        is_send::<Slot>();
+        is_send::<SharedRenderer<Vec<u8>>>();
+    }
+
+    #[test]
+    fn slots() -> io::Result<()> {
+        let mut shared = SharedRenderer::new(Renderer::for_test(vec![]));
+        let handle = shared.handle();
+        let first = handle.slot();
+        let second = handle.slot();
+        second.set("second");
+        first.set_lines(["first 1", "first 2"]);
+
+        assert!(shared.update()?);
+        assert_eq!(
+            shared.renderer.writer,
+            b"\rfirst 1\x1b[K\n\rfirst 2\x1b[K\n\rsecond\x1b[K\x1b[?25l",
+        );
+
+        shared.renderer.writer.clear();
+        assert!(!shared.update()?);
+        assert_eq!(shared.renderer.writer, b"");
+
+        drop(first);
+        assert!(shared.update()?);
+        assert_eq!(
+            shared.renderer.writer,
+            b"\x1b[2A\rsecond\x1b[K\x1b[1B\r\x1b[J\x1b[1A",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn print_from_thread() -> io::Result<()> {
+        let mut shared = SharedRenderer::new(Renderer::for_test(vec![]));
+        let handle = shared.handle();
+        let slot = handle.slot();
+        slot.set("status");
+        shared.update()?;
+        shared.renderer.writer.clear();
+
+        std::thread::spawn(move || {
+            let message = String::from("message");
+            handle.print(message.as_str());
+        })
+        .join()
+        .unwrap();
+
+        assert!(shared.wait(Duration::from_secs(10))?);
+        assert_eq!(shared.renderer.writer, b"\r\x1b[Jmessage\n\rstatus\x1b[K",);
+        Ok(())
+    }
+
+    #[test]
+    fn pending_frame() -> io::Result<()> {
+        let renderer = Renderer::for_test(vec![]).with_min_interval(Duration::from_millis(20));
+        let mut shared = SharedRenderer::new(renderer);
+        let slot = shared.handle().slot();
+        slot.set("one");
+        shared.update()?;
+        slot.set("two");
+        slot.set("three");
+        shared.update()?;
+        shared.renderer.writer.clear();
+
+        assert!(shared.wait(Duration::from_secs(10))?);
+        assert_eq!(shared.renderer.writer, b"\rthree\x1b[K");
+        Ok(())
+    }
+
+    #[test]
+    fn print_on_drop() {
+        let mut output = vec![];
+        let shared = SharedRenderer::new(Renderer::for_test(&mut output));
+        shared.handle().print("message");
+        drop(shared);
+        assert_eq!(output, b"\r\x1b[Jmessage\n\r\x1b[?25l\x1b[J\x1b[?25h");
+    }
+}
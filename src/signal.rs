@@ -0,0 +1,112 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+//! Handling of terminal-related signals.
+//!
+//! This module requires the `signals` feature.
+
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use signal_hook::SigId;
+use signal_hook::consts::{SIGINT, SIGTERM, SIGWINCH};
+use signal_hook::iterator::Signals;
+
+use crate::restore;
+
+/// Detects when the terminal is resized, by listening for `SIGWINCH`.
+///
+/// # Example
+///
+/// ```no_run
+/// use line_ui::Renderer;
+/// use line_ui::signal::ResizeSignal;
+///
+/// # fn main() -> std::io::Result<()> {
+/// let mut r = Renderer::new(std::io::stdout());
+/// let resize = ResizeSignal::new()?;
+/// loop {
+///     if resize.take() {
+///         r.check_size()?;
+///     }
+///     // ...
+/// #   break;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ResizeSignal {
+    resized: Arc<AtomicBool>,
+    id: SigId,
+}
+
+impl ResizeSignal {
+    /// Starts listening for `SIGWINCH`.
+    pub fn new() -> io::Result<Self> {
+        let resized = Arc::new(AtomicBool::new(false));
+        let id = signal_hook::flag::register(SIGWINCH, resized.clone())?;
+        Ok(ResizeSignal { resized, id })
+    }
+
+    /// Returns whether the terminal has been resized since the last call to
+    /// this method. If it has, [`Renderer::check_size`](crate::Renderer::check_size)
+    /// should be called, or a new frame rendered.
+    pub fn take(&self) -> bool {
+        self.resized.swap(false, Ordering::Relaxed)
+    }
+}
+
+impl Drop for ResizeSignal {
+    fn drop(&mut self) {
+        signal_hook::low_level::unregister(self.id);
+    }
+}
+
+/// Restores the terminal when the process receives `SIGINT` or `SIGTERM`,
+/// and then terminates the process as the signal would have.
+///
+/// Since the process does not unwind, the terminal of each
+/// [`Session`](crate::session::Session) is also taken out of raw mode. The
+/// signals are handled on a background thread. See the [`restore`] module for
+/// details.
+///
+/// # Example
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// line_ui::restore::install_panic_hook();
+/// line_ui::signal::restore_on_termination()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn restore_on_termination() -> io::Result<()> {
+    let mut signals = Signals::new([SIGINT, SIGTERM])?;
+    restore::enable();
+    std::thread::Builder::new()
+        .name("line-ui-signals".to_owned())
+        .spawn(move || {
+            if let Some(signal) = signals.forever().next() {
+                let _ = restore::restore_terminal();
+                let _ = restore::restore_modes();
+                let _ = signal_hook::low_level::emulate_default_handler(signal);
+            }
+        })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resize() -> io::Result<()> {
+        let resize = ResizeSignal::new()?;
+        assert!(!resize.take());
+        signal_hook::low_level::raise(SIGWINCH)?;
+        assert!(resize.take());
+        assert!(!resize.take());
+        Ok(())
+    }
+}
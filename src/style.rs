@@ -142,6 +142,65 @@ impl Style {
     }
 }
 
+impl Style {
+    /// Returns an equivalent style in which default colors and disabled
+    /// attributes are unspecified.
+    fn normalized(self) -> Style {
+        let color = |color: Option<Color>| color.filter(|&color| color != Color::Default);
+        let flag = |flag: Option<bool>| flag.filter(|&flag| flag);
+        Style {
+            foreground: color(self.foreground),
+            background: color(self.background),
+            bold: flag(self.bold),
+            italic: flag(self.italic),
+            underline: flag(self.underline),
+            blink: flag(self.blink),
+            invert: flag(self.invert),
+            strikethrough: flag(self.strikethrough),
+        }
+    }
+
+    /// Returns the escape sequence that changes the terminal's style from
+    /// `self` to `other`.
+    pub(crate) fn transition(self, other: Style) -> Transition {
+        Transition {
+            from: self.normalized(),
+            to: other.normalized(),
+        }
+    }
+
+    /// Returns the SGR parameters that change the terminal's style from
+    /// `from` to `self`. Both styles must be normalized.
+    fn sgr_params(self, from: Style) -> Vec<String> {
+        let mut params = Vec::new();
+        for (to, from, base) in [
+            (self.foreground, from.foreground, 3),
+            (self.background, from.background, 4),
+        ] {
+            if to != from {
+                params.push(match to {
+                    None | Some(Color::Default) => format!("{base}9"),
+                    Some(Color::Ansi(value)) => format!("{base}8;5;{value}"),
+                    Some(Color::Rgb(r, g, b)) => format!("{base}8;2;{r};{g};{b}"),
+                });
+            }
+        }
+        for (to, from, on, off) in [
+            (self.bold, from.bold, "1", "22"),
+            (self.italic, from.italic, "3", "23"),
+            (self.underline, from.underline, "4", "24"),
+            (self.blink, from.blink, "5", "25"),
+            (self.invert, from.invert, "7", "27"),
+            (self.strikethrough, from.strikethrough, "9", "29"),
+        ] {
+            if to != from {
+                params.push(if to.is_some() { on } else { off }.to_owned());
+            }
+        }
+        params
+    }
+}
+
 impl Default for Style {
     fn default() -> Self {
         Self::EMPTY
@@ -200,6 +259,36 @@ impl fmt::Display for Style {
     }
 }
 
+/// The escape sequence that changes the terminal's style, created by
+/// [`Style::transition`].
+///
+/// The sequence consists of a single SGR sequence containing only the
+/// attributes that differ between the styles, or nothing if the styles are
+/// equivalent. If it is shorter, the style is reset and then set from scratch.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Transition {
+    from: Style,
+    to: Style,
+}
+
+impl fmt::Display for Transition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.from == self.to {
+            return Ok(());
+        }
+        let changes = self.to.sgr_params(self.from).join(";");
+        let from_reset = match self.to.sgr_params(Style::EMPTY).join(";") {
+            params if params.is_empty() => params,
+            params => format!("0;{params}"),
+        };
+        if from_reset.len() < changes.len() {
+            write!(f, "\x1b[{from_reset}m")
+        } else {
+            write!(f, "\x1b[{changes}m")
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Write;
@@ -282,6 +371,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transition() {
+        let transition = |from: Style, to: Style| from.transition(to).to_string();
+        assert_eq!(transition(Style::EMPTY, Style::EMPTY), "");
+        assert_eq!(transition(STYLE_1, STYLE_1), "");
+        assert_eq!(transition(Style::EMPTY, STYLE_1), "\x1b[38;5;1;1m");
+        assert_eq!(transition(STYLE_1, Style::EMPTY), "\x1b[m");
+        assert_eq!(transition(STYLE_1, STYLE_2), "\x1b[0;38;5;2;3m");
+        assert_eq!(transition(STYLE_1, STYLE_1 + Style::ITALIC), "\x1b[3m",);
+        assert_eq!(
+            transition(STYLE_1 + Style::UNDERLINE, Style::BOLD),
+            "\x1b[0;1m",
+        );
+        assert_eq!(
+            transition(Style::bg((1, 2, 3)), Style::bg((1, 2, 3)) + Style::INVERT),
+            "\x1b[7m",
+        );
+    }
+
+    #[test]
+    fn transition_normalized() {
+        let explicit_default = Style {
+            foreground: Some(Color::Default),
+            bold: Some(false),
+            ..Style::EMPTY
+        };
+        assert_eq!(Style::EMPTY.transition(explicit_default).to_string(), "");
+        assert_eq!(
+            Style::fg(1).transition(explicit_default).to_string(),
+            "\x1b[m",
+        );
+    }
+
     #[test]
     fn print_default_and_rgb() {
         let mut output = vec![];
@@ -0,0 +1,668 @@
+/*
+ * Copyright (c) 2025 Jasmine Tai. All rights reserved.
+ */
+
+//! Utilities for testing user interfaces.
+//!
+//! A [`Screen`] is a small virtual terminal, which interprets the output of a
+//! [`Renderer`] the way that a real terminal would. This allows tests to make
+//! assertions about what is displayed, rather than about the exact escape
+//! sequences that were written.
+//!
+//! # Example
+//!
+//! ```
+//! use line_ui::Style;
+//! use line_ui::element::{Cursor, IntoElement};
+//! use line_ui::testing::Screen;
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let mut r = Screen::new(20, 5).into_renderer();
+//! r.reset()?
+//!     .render("hello".into_element())?
+//!     .render(("foo".styled(Style::BOLD), Cursor))?
+//!     .finish()?;
+//!
+//! let screen = r.writer();
+//! assert_eq!(screen.line(0), "hello");
+//! assert_eq!(screen.line(1), "foo");
+//! assert_eq!(screen.cell(0, 1).style(), Style::BOLD);
+//! assert_eq!(screen.cursor(), (3, 1));
+//! assert!(screen.cursor_visible());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{self, Write};
+
+use crate::element::CursorShape;
+use crate::{Color, Renderer, Style};
+
+/// A single character cell of a [`Screen`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    symbol: String,
+    style: Style,
+}
+
+impl Cell {
+    /// The text displayed in this cell. This is a space if nothing has been
+    /// written to the cell, and empty if the cell is covered by a wide
+    /// character in the previous cell.
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// The style of the text in this cell.
+    ///
+    /// Attributes that are turned off are unspecified (i.e., [`None`]), so an
+    /// unstyled cell has the style [`Style::EMPTY`].
+    pub fn style(&self) -> Style {
+        self.style
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            symbol: " ".to_owned(),
+            style: Style::EMPTY,
+        }
+    }
+}
+
+/// The cells of a screen, indexed by row and then by column.
+type Grid = Vec<Vec<Cell>>;
+
+/// A virtual terminal that keeps track of what would be displayed.
+///
+/// Bytes written to the `Screen` are interpreted as terminal output, in raw
+/// mode (i.e., a newline moves the cursor down without returning it to the
+/// start of the line). Unsupported escape sequences are ignored.
+///
+/// Positions are given as `(column, row)`, starting from `(0, 0)` at the top
+/// left of the screen.
+///
+/// See the [module-level documentation](self) for an example.
+#[derive(Debug, Clone)]
+pub struct Screen {
+    size: (u16, u16),
+    grid: Grid,
+    scrollback: Vec<String>,
+    cursor: (u16, u16),
+    /// The cursor position saved by DECSC.
+    saved_cursor: (u16, u16),
+    /// The first and last rows of the scroll region.
+    scroll_region: (u16, u16),
+    /// Whether the cursor is past the last column, so that the next printed
+    /// character wraps to the next line.
+    pending_wrap: bool,
+    cursor_visible: bool,
+    cursor_shape: Option<CursorShape>,
+    /// The contents of the main screen and the cursor position, saved while
+    /// the alternate screen is displayed.
+    main_screen: Option<(Grid, (u16, u16))>,
+    style: Style,
+    /// Bytes of an incomplete escape sequence or character.
+    input: Vec<u8>,
+}
+
+impl Screen {
+    /// Creates a new, blank screen with the given number of columns and
+    /// rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either dimension is zero.
+    pub fn new(columns: u16, rows: u16) -> Self {
+        assert!(columns != 0 && rows != 0, "screen size must be nonzero");
+        Screen {
+            size: (columns, rows),
+            grid: vec![vec![Cell::default(); columns as usize]; rows as usize],
+            scrollback: Vec::new(),
+            cursor: (0, 0),
+            saved_cursor: (0, 0),
+            scroll_region: (0, rows - 1),
+            pending_wrap: false,
+            cursor_visible: true,
+            cursor_shape: None,
+            main_screen: None,
+            style: Style::EMPTY,
+            input: Vec::new(),
+        }
+    }
+
+    /// Creates a [`Renderer`] that writes to this screen, using its size as
+    /// the size of the terminal.
+    pub fn into_renderer(self) -> Renderer<Screen> {
+        let size = self.size;
+        Renderer::new(self).with_size_source(move || Some(size))
+    }
+
+    /// Returns the size of the screen, as `(columns, rows)`.
+    pub fn size(&self) -> (u16, u16) {
+        self.size
+    }
+
+    /// Returns the cell at the given position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position is outside of the screen.
+    pub fn cell(&self, column: u16, row: u16) -> &Cell {
+        &self.grid[row as usize][column as usize]
+    }
+
+    /// Returns the text of a row, without trailing whitespace.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row is outside of the screen.
+    pub fn line(&self, row: u16) -> String {
+        row_text(&self.grid[row as usize])
+    }
+
+    /// Returns the text of every row, without trailing whitespace.
+    pub fn lines(&self) -> Vec<String> {
+        self.grid.iter().map(|row| row_text(row)).collect()
+    }
+
+    /// Returns the text of the rows that have scrolled off the top of the
+    /// screen, oldest first.
+    pub fn scrollback(&self) -> &[String] {
+        &self.scrollback
+    }
+
+    /// Returns the position of the cursor.
+    pub fn cursor(&self) -> (u16, u16) {
+        self.cursor
+    }
+
+    /// Returns whether the cursor is shown.
+    pub fn cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
+    /// Returns the shape of the cursor, or `None` if it is the terminal's
+    /// default shape.
+    pub fn cursor_shape(&self) -> Option<CursorShape> {
+        self.cursor_shape
+    }
+
+    /// Returns whether the alternate screen is displayed.
+    pub fn is_alternate_screen(&self) -> bool {
+        self.main_screen.is_some()
+    }
+
+    /// Returns the style that subsequently-printed text would have.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Interprets as much of the buffered input as possible.
+    fn process(&mut self) {
+        let input = std::mem::take(&mut self.input);
+        let mut rest = &input[..];
+        while let Some(&byte) = rest.first() {
+            let consumed = match byte {
+                0x1b => self.escape(rest),
+                b'\r' => {
+                    self.move_to_column(0);
+                    Some(1)
+                }
+                b'\n' => {
+                    self.line_feed();
+                    Some(1)
+                }
+                b'\x08' => {
+                    self.move_to_column(self.cursor.0.saturating_sub(1));
+                    Some(1)
+                }
+                0..0x20 | 0x7f => Some(1),
+                _ => self.character(rest),
+            };
+            match consumed {
+                Some(consumed) => rest = &rest[consumed..],
+                None => break,
+            }
+        }
+        self.input = rest.to_vec();
+    }
+
+    /// Interprets an escape sequence, returning the number of bytes consumed,
+    /// or `None` if the sequence is incomplete.
+    fn escape(&mut self, input: &[u8]) -> Option<usize> {
+        match *input.get(1)? {
+            b'[' => {
+                let end = 2 + input[2..]
+                    .iter()
+                    .position(|byte| (0x40..0x7f).contains(byte))?;
+                let params = &input[2..end];
+                self.csi(params, input[end]);
+                Some(end + 1)
+            }
+            b']' => {
+                // Operating system commands are terminated by BEL or ST.
+                (2..input.len()).find_map(|i| match input[i] {
+                    0x07 => Some(i + 1),
+                    0x1b if input.get(i + 1) == Some(&b'\\') => Some(i + 2),
+                    _ => None,
+                })
+            }
+            b'7' => {
+                self.saved_cursor = self.cursor;
+                Some(2)
+            }
+            b'8' => {
+                let (column, row) = self.saved_cursor;
+                self.move_to(column, row);
+                Some(2)
+            }
+            _ => Some(2),
+        }
+    }
+
+    /// Interprets a control sequence.
+    fn csi(&mut self, params: &[u8], action: u8) {
+        // The only supported sequence with an intermediate byte is DECSCUSR.
+        if let Some(params) = params.strip_suffix(b" ") {
+            if action == b'q' {
+                let code = std::str::from_utf8(params).unwrap_or_default();
+                self.cursor_shape = CursorShape::from_code(code.parse().unwrap_or(0));
+            }
+            return;
+        }
+        let private = params.first() == Some(&b'?');
+        let params = std::str::from_utf8(params).unwrap_or_default();
+        let numbers: Vec<u16> = params
+            .trim_start_matches('?')
+            .split(';')
+            .map(|param| param.parse().unwrap_or(0))
+            .collect();
+        let first = numbers[0];
+        let count = first.max(1);
+        let (column, row) = self.cursor;
+        let (columns, rows) = self.size;
+        match (private, action) {
+            (false, b'A') => self.move_to(column, row.saturating_sub(count)),
+            (false, b'B') => self.move_to(column, row.saturating_add(count)),
+            (false, b'C') => self.move_to(column.saturating_add(count), row),
+            (false, b'D') => self.move_to(column.saturating_sub(count), row),
+            (false, b'G') => self.move_to(count - 1, row),
+            (false, b'H' | b'f') => {
+                let column = numbers.get(1).copied().unwrap_or(0).max(1);
+                self.move_to(column - 1, count - 1);
+            }
+            (false, b'J') => match first {
+                0 => {
+                    self.erase(row, column..columns);
+                    for row in row + 1..rows {
+                        self.erase(row, 0..columns);
+                    }
+                }
+                1 => {
+                    for row in 0..row {
+                        self.erase(row, 0..columns);
+                    }
+                    self.erase(row, 0..column + 1);
+                }
+                2 => {
+                    for row in 0..rows {
+                        self.erase(row, 0..columns);
+                    }
+                }
+                3 => self.scrollback.clear(),
+                _ => {}
+            },
+            (false, b'K') => match first {
+                0 => self.erase(row, column..columns),
+                1 => self.erase(row, 0..column + 1),
+                2 => self.erase(row, 0..columns),
+                _ => {}
+            },
+            (false, b'm') => self.select_graphic_rendition(&numbers),
+            (false, b'r') => {
+                let bottom = match numbers.get(1) {
+                    Some(&bottom) if bottom != 0 => bottom.min(rows),
+                    _ => rows,
+                };
+                if count < bottom {
+                    self.scroll_region = (count - 1, bottom - 1);
+                    self.move_to(0, 0);
+                }
+            }
+            (true, b'h' | b'l') => {
+                let enable = action == b'h';
+                for mode in numbers {
+                    match mode {
+                        25 => self.cursor_visible = enable,
+                        1049 => self.set_alternate_screen(enable),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Applies the parameters of an SGR sequence to the current style.
+    fn select_graphic_rendition(&mut self, params: &[u16]) {
+        let style = &mut self.style;
+        let mut params = params.iter().copied();
+        while let Some(param) = params.next() {
+            match param {
+                0 => *style = Style::EMPTY,
+                1 => style.bold = Some(true),
+                3 => style.italic = Some(true),
+                4 => style.underline = Some(true),
+                5 => style.blink = Some(true),
+                7 => style.invert = Some(true),
+                9 => style.strikethrough = Some(true),
+                22 => style.bold = None,
+                23 => style.italic = None,
+                24 => style.underline = None,
+                25 => style.blink = None,
+                27 => style.invert = None,
+                29 => style.strikethrough = None,
+                30..=37 => style.foreground = Some(Color::Ansi(param as u8 - 30)),
+                38 => style.foreground = extended_color(&mut params),
+                39 => style.foreground = None,
+                40..=47 => style.background = Some(Color::Ansi(param as u8 - 40)),
+                48 => style.background = extended_color(&mut params),
+                49 => style.background = None,
+                90..=97 => style.foreground = Some(Color::Ansi(param as u8 - 90 + 8)),
+                100..=107 => style.background = Some(Color::Ansi(param as u8 - 100 + 8)),
+                _ => {}
+            }
+        }
+    }
+
+    /// Prints a character, returning the number of bytes consumed, or `None`
+    /// if the character is incomplete.
+    fn character(&mut self, input: &[u8]) -> Option<usize> {
+        let len = match input[0] {
+            0xc0..0xe0 => 2,
+            0xe0..0xf0 => 3,
+            0xf0..0xf8 => 4,
+            _ => 1,
+        };
+        let bytes = input.get(..len)?;
+        match std::str::from_utf8(bytes) {
+            Ok(text) => self.print(text),
+            Err(_) => self.print("\u{fffd}"),
+        }
+        Some(len)
+    }
+
+    /// Prints a single character at the cursor position.
+    fn print(&mut self, text: &str) {
+        let width = crate::width(text).min(2) as u16;
+        let columns = self.size.0;
+        if width == 0 {
+            // Combine with the previously-printed character.
+            let (column, row) = self.cursor;
+            let column = if self.pending_wrap {
+                column
+            } else {
+                column.saturating_sub(1)
+            };
+            let row = &mut self.grid[row as usize];
+            let column = (0..=column as usize)
+                .rev()
+                .find(|&column| !row[column].symbol.is_empty())
+                .unwrap_or(0);
+            row[column].symbol.push_str(text);
+            return;
+        }
+        // A wide character that cannot fit even on an empty row takes up the
+        // whole row instead.
+        let width = width.min(columns);
+        if self.pending_wrap || self.cursor.0 + width > columns {
+            self.cursor.0 = 0;
+            self.line_feed();
+        }
+
+        let (column, row) = self.cursor;
+        self.split_wide(column, row);
+        self.split_wide(column + width, row);
+        let style = self.style;
+        let row = &mut self.grid[row as usize];
+        row[column as usize] = Cell {
+            symbol: text.to_owned(),
+            style,
+        };
+        if width == 2 {
+            row[column as usize + 1] = Cell {
+                symbol: String::new(),
+                style,
+            };
+        }
+
+        if column + width < columns {
+            self.cursor.0 = column + width;
+        } else {
+            self.cursor.0 = columns - 1;
+            self.pending_wrap = true;
+        }
+    }
+
+    /// Replaces any wide character that straddles the left edge of the given
+    /// cell with spaces, since it is about to be partially overwritten.
+    fn split_wide(&mut self, column: u16, row: u16) {
+        let row = &mut self.grid[row as usize];
+        let column = column as usize;
+        if column < row.len() && column > 0 && row[column].symbol.is_empty() {
+            row[column - 1] = Cell::default();
+            row[column] = Cell::default();
+        }
+    }
+
+    /// Blanks the given cells of a row.
+    fn erase(&mut self, row: u16, columns: std::ops::Range<u16>) {
+        self.pending_wrap = false;
+        let end = columns.end.min(self.size.0);
+        self.split_wide(columns.start, row);
+        self.split_wide(end, row);
+        for cell in &mut self.grid[row as usize][columns.start as usize..end as usize] {
+            *cell = Cell::default();
+        }
+    }
+
+    /// Switches between the main screen and a blank alternate screen.
+    fn set_alternate_screen(&mut self, enable: bool) {
+        if enable && self.main_screen.is_none() {
+            let blank = vec![vec![Cell::default(); self.size.0 as usize]; self.size.1 as usize];
+            let grid = std::mem::replace(&mut self.grid, blank);
+            self.main_screen = Some((grid, self.cursor));
+        } else if !enable && let Some((grid, cursor)) = self.main_screen.take() {
+            self.grid = grid;
+            self.cursor = cursor;
+        }
+        self.pending_wrap = false;
+    }
+
+    /// Moves the cursor down a row, scrolling the scroll region if it is on
+    /// the region's last row.
+    fn line_feed(&mut self) {
+        self.pending_wrap = false;
+        let (top, bottom) = self.scroll_region;
+        if self.cursor.1 == bottom {
+            let row = self.grid.remove(top as usize);
+            // The alternate screen has no history, and neither does a scroll
+            // region that doesn't start at the top of the screen.
+            if self.main_screen.is_none() && top == 0 {
+                self.scrollback.push(row_text(&row));
+            }
+            let blank = vec![Cell::default(); self.size.0 as usize];
+            self.grid.insert(bottom as usize, blank);
+        } else if self.cursor.1 + 1 < self.size.1 {
+            self.cursor.1 += 1;
+        }
+    }
+
+    /// Moves the cursor to the given column of the current row.
+    fn move_to_column(&mut self, column: u16) {
+        self.move_to(column, self.cursor.1);
+    }
+
+    /// Moves the cursor to the given position, clamped to the screen.
+    fn move_to(&mut self, column: u16, row: u16) {
+        self.pending_wrap = false;
+        self.cursor = (column.min(self.size.0 - 1), row.min(self.size.1 - 1));
+    }
+}
+
+impl Write for Screen {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.input.extend_from_slice(buf);
+        self.process();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Returns the text of a row, without trailing whitespace.
+fn row_text(row: &[Cell]) -> String {
+    let text: String = row.iter().map(|cell| cell.symbol()).collect();
+    text.trim_end().to_owned()
+}
+
+/// Parses the color of an extended SGR parameter (38 or 48).
+fn extended_color(params: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    match params.next()? {
+        5 => Some(Color::Ansi(params.next()? as u8)),
+        2 => {
+            let mut component = || params.next().map(|value| value as u8);
+            Some(Color::Rgb(component()?, component()?, component()?))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Mode;
+    use crate::element::{Cursor, IntoElement};
+
+    use super::*;
+
+    #[test]
+    fn frames() -> io::Result<()> {
+        let mut r = Screen::new(20, 5).into_renderer();
+        r.reset()?
+            .render("trans rights".styled(Style::BOLD))?
+            .render(("enby ".into_element(), Cursor, "rights".into_element()))?
+            .render("human rights".styled(Style::fg(3) + Style::bg((1, 2, 3))))?
+            .finish()?;
+        let screen = r.writer();
+        assert_eq!(
+            screen.lines(),
+            ["trans rights", "enby rights", "human rights", "", ""],
+        );
+        assert_eq!(screen.cell(0, 0).style(), Style::BOLD);
+        assert_eq!(screen.cell(0, 1).style(), Style::EMPTY);
+        assert_eq!(
+            screen.cell(11, 2).style(),
+            Style::fg(3) + Style::bg((1, 2, 3)),
+        );
+        assert_eq!(screen.cell(12, 2).style(), Style::EMPTY);
+        assert_eq!(screen.cursor(), (5, 1));
+        assert!(screen.cursor_visible());
+
+        r.reset()?.render("all rights".into_element())?.finish()?;
+        let screen = r.writer();
+        assert_eq!(screen.lines(), ["all rights", "", "", "", ""]);
+        assert_eq!(screen.cell(0, 0).style(), Style::EMPTY);
+        assert!(!screen.cursor_visible());
+
+        r.clear()?;
+        assert_eq!(r.writer().lines(), ["", "", "", "", ""]);
+        assert_eq!(r.writer().cursor(), (0, 0));
+        assert!(r.writer().cursor_visible());
+        Ok(())
+    }
+
+    #[test]
+    fn print_scrolls() -> io::Result<()> {
+        let mut r = Screen::new(10, 3).into_renderer();
+        r.reset()?.render("status".into_element())?.finish()?;
+        for line in ["one", "two", "three"] {
+            r.print(line)?;
+        }
+        let screen = r.writer();
+        assert_eq!(screen.scrollback(), ["one"]);
+        assert_eq!(screen.lines(), ["two", "three", "status"]);
+        Ok(())
+    }
+
+    #[test]
+    fn full_screen() -> io::Result<()> {
+        let mut screen = Screen::new(20, 3);
+        write!(screen, "$ prompt\r\n")?;
+        let mut r = screen.into_renderer().with_mode(Mode::FullScreen);
+        r.reset()?
+            .render("trans rights".into_element())?
+            .render("enby rights".into_element())?
+            .finish()?;
+        r.print("hello")?;
+        let screen = r.writer();
+        assert!(screen.is_alternate_screen());
+        assert_eq!(screen.lines(), ["trans rights", "enby rights", ""]);
+
+        r.clear()?;
+        let screen = r.writer();
+        assert!(!screen.is_alternate_screen());
+        assert_eq!(screen.lines(), ["$ prompt", "hello", ""]);
+        assert_eq!(screen.cursor(), (0, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn wrap() {
+        let mut screen = Screen::new(4, 2);
+        write!(screen, "abcd").unwrap();
+        assert_eq!(screen.cursor(), (3, 0));
+        write!(screen, "ef").unwrap();
+        assert_eq!(screen.lines(), ["abcd", "ef"]);
+        assert_eq!(screen.cursor(), (2, 1));
+    }
+
+    #[test]
+    fn wide_characters() {
+        let mut screen = Screen::new(5, 2);
+        write!(screen, "a日本").unwrap();
+        assert_eq!(screen.line(0), "a日本");
+        assert_eq!(screen.cell(1, 0).symbol(), "日");
+        assert_eq!(screen.cell(2, 0).symbol(), "");
+        assert_eq!(screen.cursor(), (4, 0));
+
+        // Overwriting half of a wide character erases the other half.
+        write!(screen, "\r\x1b[2Cx").unwrap();
+        assert_eq!(screen.line(0), "a x本");
+
+        // A wide character that does not fit is wrapped to the next line.
+        write!(screen, "\r\n12345日").unwrap();
+        assert_eq!(screen.lines(), ["12345", "日"]);
+        assert_eq!(screen.scrollback(), ["a x本"]);
+
+        // A wide character that is wider than the screen fills the row.
+        let mut screen = Screen::new(1, 2);
+        write!(screen, "日本").unwrap();
+        assert_eq!(screen.lines(), ["日", "本"]);
+        assert_eq!(screen.cursor(), (0, 1));
+    }
+
+    #[test]
+    fn incomplete_input() {
+        let mut screen = Screen::new(10, 2);
+        let bytes = "\x1b[1mé".as_bytes();
+        for byte in bytes {
+            screen.write_all(&[*byte]).unwrap();
+        }
+        assert_eq!(screen.line(0), "é");
+        assert_eq!(screen.cell(0, 0).style(), Style::BOLD);
+    }
+}